@@ -0,0 +1,102 @@
+//! Day 1: Trebuchet?! Recovers calibration values from the first and last digit of each line.
+//!
+//! Use [`Day1`] through [`aoc_common::Solution`], or the solvers below on the lines directly.
+
+use aoc_common::{Generator, ParseError, Rng, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+
+/// Sums the two digit numbers made of the first and last digit of every line. Lines without digits count as 0.
+pub fn calibration_sum(lines: &[String]) -> i32 {
+    let digit: Regex = Regex::new(r"\d").unwrap();
+    
+    let mut sum = 0;
+
+    lines.iter().for_each(|line| {
+        let result: Vec<i32> = digit.find_iter(line).filter_map(|digits| digits.as_str().parse::<i32>().ok()).collect();
+        if result.is_empty() {
+            trace!("{} -> 0", line);
+            return;
+        }
+        trace!("{} -> {}{}", line, &result[0], &result[result.len() - 1]);
+        sum += &result[0] * 10 + result[result.len()- 1];
+    });
+    sum
+}
+
+/// Like [`calibration_sum`], with digits that are spelled out as words counting as well.
+pub fn spelled_calibration_sum(lines: &[String]) -> i32 {
+    let digit: Regex = Regex::new(r"(\d)").unwrap();
+    
+    let mut sum = 0;
+
+    let replacements = vec![
+        ("one", "o1e"),
+        ("two", "t2o"),
+        ("three", "t3e"),
+        ("four", "f4r"),
+        ("five", "f5e"),
+        ("six", "s6x"),
+        ("seven", "s7n"),
+        ("eight", "e8t"),
+        ("nine", "n9e"),
+    ];
+    lines.iter().for_each(|line| {
+        let mut processed = line.to_lowercase().to_string();
+        for (from, to) in replacements.iter() {
+            processed = processed.replace(from, to);
+        }
+        let numbers: Vec<i32> = digit.find_iter(processed.as_str()).filter_map(|result| result.as_str().parse::<i32>().ok()).collect();
+        if numbers.is_empty() {
+            trace!("{} -> 0", line);
+            return;
+        }
+        trace!("{} -> {}{}", line, &numbers[0], &numbers[numbers.len() - 1]);
+        sum += &numbers[0] * 10 + numbers[numbers.len()- 1];
+    });
+    sum
+}
+
+/// The puzzle, the model is the list of lines.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Model = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(aoc_common::lines(input))
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<i32, SolveError> {
+        Ok(calibration_sum(lines))
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<i32, SolveError> {
+        Ok(spelled_calibration_sum(lines))
+    }
+}
+
+/// `size` lines of letters mixed with digits, some of them spelled out. A few lines have no digits at all.
+impl Generator for Day1 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const SPELLED: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+        let mut input = String::new();
+        for _ in 0..size {
+            let without_digits = rng.chance(0.05);
+            for _ in 0..rng.between(1, 8) {
+                match rng.below(3) {
+                    0 if !without_digits => input.push((b'1' + rng.below(9) as u8) as char),
+                    1 if !without_digits => input += *rng.choose(&SPELLED),
+                    _ => {
+                        let length = rng.below(4) + 1;
+                        input += &rng.word(length);
+                    }
+                }
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
@@ -0,0 +1,178 @@
+//! Day 10: Pipe Maze. Follows the loop of pipes through the start tile, and counts the tiles the loop encloses.
+
+use aoc_common::{generate, Cell, Color, Direction, Generator, Grid, ParseError, Position, Render, Rng, Solution, SolveError};
+use log::{debug, trace};
+
+/// A tile of the maze, with the directions its pipe connects in the order of `Direction::ALL`.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct ConnectorType {
+    pub directions: [bool; 4],
+    pub symbol: char
+}
+
+const CONNECTORS: [ConnectorType; 8] = [
+    ConnectorType { directions: [false, false, false, false], symbol: 'S' },
+    ConnectorType { directions: [false, false, false, false], symbol: '.' },
+    ConnectorType { directions: [true, false, true, false], symbol: '|' },
+    ConnectorType { directions: [false, true, false, true], symbol: '-' },
+    
+    ConnectorType { directions: [true, true, false, false], symbol: 'L' },
+    ConnectorType { directions: [true, false, false, true], symbol: 'J' },
+    ConnectorType { directions: [false, false, true, true], symbol: '7' },
+    ConnectorType { directions: [false, true, true, false], symbol: 'F' },
+];
+
+/// Follows the pipes from the start for every shape the start could have, until they lead back to it. Returns the
+/// shape that closes the loop and the positions along the loop, or `None` if no shape does.
+pub fn find_loop(connectors: &Grid<ConnectorType>, start: (usize, usize)) -> Option<(ConnectorType, Vec<(usize, usize)>)> {
+    for start_connector in &CONNECTORS[2..8] {
+        trace!("checking with S as {}", start_connector.symbol);
+        let connector_at = |position: (usize, usize)| if position == start { start_connector } else { &connectors[position] };
+        let mut pipe_loop = vec![start];
+        let mut position = start;
+        let mut came_from: Option<Direction> = None;
+        loop {
+            let direction = Direction::ALL.into_iter()
+                .find(|direction| connector_at(position).directions[direction.index()] && Some(*direction) != came_from)
+                .unwrap();
+            let Some(next) = connectors.step(position, direction) else {
+                trace!("Ran into a dead end after {} steps (no node)", pipe_loop.len());
+                break;
+            };
+            if !connector_at(next).directions[direction.opposite().index()] {
+                trace!("Ran into a dead end after {} steps (incompatible receiver)", pipe_loop.len());
+                break;
+            }
+            if next == start {
+                debug!("Found a loop after {} steps", pipe_loop.len());
+                return Some((*start_connector, pipe_loop));
+            }
+            pipe_loop.push(next);
+            position = next;
+            came_from = Some(direction.opposite());
+        }
+    }
+    None
+}
+
+/// Scans every row from the left: a tile is enclosed once an odd number of loop pipes leading north were crossed.
+pub fn enclosed_tiles(maze: &Maze) -> Grid<bool> {
+    let mut on_loop = Grid::filled(maze.tiles.width(), maze.tiles.height(), false);
+    for position in &maze.pipe_loop {
+        on_loop[*position] = true;
+    }
+    let mut enclosed = Grid::filled(maze.tiles.width(), maze.tiles.height(), false);
+    for y in 0..maze.tiles.height() {
+        let mut inside = false;
+        for x in 0..maze.tiles.width() {
+            if on_loop[(x, y)] {
+                inside ^= maze.tiles[(x, y)].directions[Direction::North.index()];
+            } else {
+                enclosed[(x, y)] = inside;
+            }
+        }
+    }
+    enclosed
+}
+
+/// The number of tiles the loop encloses.
+pub fn find_enclosed_area(maze: &Maze) -> usize {
+    enclosed_tiles(maze).iter().filter(|(_, enclosed)| **enclosed).count()
+}
+
+pub struct Maze {
+    /// The tiles, with the start replaced by the pipe that closes the loop.
+    pub tiles: Grid<ConnectorType>,
+    /// The tiles along the loop, starting at the start.
+    pub pipe_loop: Vec<(usize, usize)>,
+}
+
+/// Parses the tiles and finds the loop through the start.
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    let mut tiles = Grid::from_str(input, |c| CONNECTORS.iter().find(|connector| connector.symbol == c).copied())?;
+    let Some(start) = tiles.position(|connector| connector.symbol == 'S') else {
+        return Err(ParseError::new(Position::start_of(1), input.lines().next().unwrap_or(""), "expected a starting position S"));
+    };
+    let Some((start_connector, pipe_loop)) = find_loop(&tiles, start) else {
+        return Err(ParseError::new(Position::new(start.1 + 1, start.0 + 1), "S", "no loop of pipes goes through the start"));
+    };
+    tiles[start] = start_connector;
+    Ok(Maze { tiles, pipe_loop })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Model = Maze;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Maze, ParseError> {
+        parse(input)
+    }
+
+    fn part1(maze: &Maze) -> Result<usize, SolveError> {
+        Ok(maze.pipe_loop.len() / 2)
+    }
+
+    fn part2(maze: &Maze) -> Result<usize, SolveError> {
+        Ok(find_enclosed_area(maze))
+    }
+}
+
+/// The pipes as box drawing characters, the loop in yellow and the others in grey. Part 1 marks the tile farthest from
+/// the start along the loop in red, part 2 the enclosed tiles in green.
+impl Render for Day10 {
+    fn render(maze: &Maze, part: u8) -> Result<Grid<Cell>, SolveError> {
+        let mut picture = maze.tiles.map(|connector| {
+            let glyph = match connector.symbol {
+                '|' => '│',
+                '-' => '─',
+                'L' => '└',
+                'J' => '┘',
+                '7' => '┐',
+                'F' => '┌',
+                _ => ' ',
+            };
+            Cell::new(glyph, Color::GREY)
+        });
+        for position in &maze.pipe_loop {
+            picture[*position].foreground = Color::YELLOW;
+        }
+        if part == 1 {
+            picture[maze.pipe_loop[maze.pipe_loop.len() / 2]].background = Color::RED;
+        } else {
+            for (position, enclosed) in enclosed_tiles(maze).iter() {
+                if *enclosed {
+                    picture[position].background = Color::GREEN;
+                }
+            }
+        }
+        Ok(picture)
+    }
+}
+
+/// A `size` by `size` field, at least 2 by 2, with a random loop of pipes between junk pipes. The tiles next to the
+/// start that are not on the loop are ground, so the loop is the only way the start can be closed.
+impl Generator for Day10 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(2);
+        const JUNK: [char; 7] = ['.', '|', '-', 'L', 'J', '7', 'F'];
+        let mut tiles = Grid::new(size, size, (0..size * size).map(|_| *rng.choose(&JUNK)).collect());
+        let pipe_loop = generate::polygon(rng, size - 1, size - 1);
+        for (i, &position) in pipe_loop.iter().enumerate() {
+            let towards = |other: (usize, usize)| Direction::ALL.into_iter().find(|&direction| tiles.step(position, direction) == Some(other)).unwrap();
+            let ends = [towards(pipe_loop[(i + pipe_loop.len() - 1) % pipe_loop.len()]), towards(pipe_loop[(i + 1) % pipe_loop.len()])];
+            let connector = CONNECTORS[2..].iter().find(|connector| ends.iter().all(|end| connector.directions[end.index()])).unwrap();
+            tiles[position] = connector.symbol;
+        }
+        let start = *rng.choose(&pipe_loop);
+        for neighbour in tiles.neighbours4(start).collect::<Vec<_>>() {
+            if !pipe_loop.contains(&neighbour) {
+                tiles[neighbour] = '.';
+            }
+        }
+        tiles[start] = 'S';
+        tiles.to_string()
+    }
+}
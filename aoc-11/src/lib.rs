@@ -0,0 +1,92 @@
+//! Day 11: Cosmic Expansion. Sums the distances between all pairs of galaxies after empty rows and columns grow.
+
+use aoc_common::{Generator, Grid, ParseError, Rng, Solution, SolveError};
+use std::collections::HashSet;
+
+/// A galaxy.
+#[derive(Eq, PartialEq, Clone)]
+pub struct Star {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Star {
+    pub fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The Manhattan distance between two galaxies.
+    pub fn distance(&self, other: &Star) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+fn compute_distances_sum(stars: &[Star]) -> isize {
+    stars.iter().enumerate().map(|(i, star)|
+        stars.iter().skip(i+1).map(|other_star| {
+            star.distance(other_star)
+        }).sum::<isize>()
+    ).sum::<isize>()
+}
+
+fn expand_stars(stars: &mut Vec<Star>, occupied_rows: &HashSet<isize>, occupied_cols: &HashSet<isize>, distance: isize) {
+    for star in stars {
+        star.x += (0..star.x).filter(|x| !occupied_cols.contains(x)).count() as isize * (distance-1);
+        star.y += (0..star.y).filter(|y| !occupied_rows.contains(y)).count() as isize * (distance-1);
+    }
+}
+
+/// Parses the image into the positions of its galaxies.
+pub fn parse(input: &str) -> Result<Vec<Star>, ParseError> {
+    let image = Grid::from_str(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(image.iter().filter(|(_, is_star)| **is_star).map(|((x, y), _)| Star::new(x as isize, y as isize)).collect())
+}
+
+/// Sums the distances between all pairs of galaxies once every empty row and column is `distance` wide.
+pub fn expanded_distances_sum(stars: &[Star], distance: isize) -> isize {
+    let mut stars = stars.to_vec();
+    let occupied_rows: HashSet<isize> = HashSet::from_iter(stars.iter().map(|s| s.y));
+    let occupied_cols: HashSet<isize> = HashSet::from_iter(stars.iter().map(|s| s.x));
+    expand_stars(&mut stars, &occupied_rows, &occupied_cols, distance);
+    compute_distances_sum(&stars)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Model = Vec<Star>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<Star>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(stars: &Vec<Star>) -> Result<isize, SolveError> {
+        Ok(expanded_distances_sum(stars, 2))
+    }
+
+    fn part2(stars: &Vec<Star>) -> Result<isize, SolveError> {
+        Ok(expanded_distances_sum(stars, 1000000))
+    }
+}
+
+/// A `size` by `size` image with a galaxy on about one in ten pixels, and some rows and columns left empty.
+impl Generator for Day11 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let empty_rows = (0..size).map(|_| rng.chance(0.1)).collect::<Vec<_>>();
+        let empty_columns = (0..size).map(|_| rng.chance(0.1)).collect::<Vec<_>>();
+        let mut input = String::new();
+        for empty_row in empty_rows {
+            for &empty_column in &empty_columns {
+                input.push(if !empty_row && !empty_column && rng.chance(0.1) { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
@@ -0,0 +1,164 @@
+//! Day 12: Hot Springs. Counts the arrangements of damaged springs that fit both a row with unknown springs and
+//! the sizes of its groups of damaged ones.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+use std::collections::HashMap;
+use regex::Regex;
+
+/// The condition of a spring.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum State {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl State {
+    /// The state written as `.`, `#` or `?`.
+    pub fn from_char(c: char) -> Option<State> {
+        match c {
+            '.' => Some(State::Operational),
+            '#' => Some(State::Damaged),
+            '?' => Some(State::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// Counts the arrangements of `states` with damaged groups of the sizes in `groups`, the last `preceding_damaged`
+/// springs before `states` having been damaged, or `None` if there are too many to count in a `usize`. `states` and
+/// `groups` are the rests of one row, and `cache` remembers the counts of the rests by their lengths.
+pub fn get_solution_count(states: &[State], groups: &[usize], preceding_damaged: usize, cache: &mut HashMap<(usize, usize, usize), Option<usize>>) -> Option<usize> {
+    // a run of damaged springs longer than the next group never fits
+    if preceding_damaged > 0 && groups.first().is_none_or(|&group| preceding_damaged > group) {
+        return Some(0);
+    }
+    if states.is_empty(){
+        return if groups.is_empty() && preceding_damaged == 0 || groups.len() == 1 && groups[0] == preceding_damaged {
+            Some(1)
+        } else {
+            Some(0)
+        }
+    }
+    let key = (states.len(), groups.len(), preceding_damaged);
+    if let Some(&count) = cache.get(&key) {
+        return count;
+    }
+    let operational = |cache: &mut HashMap<(usize, usize, usize), Option<usize>>| if preceding_damaged != 0 {
+        if groups.is_empty() || groups[0] != preceding_damaged {
+            Some(0)
+        } else {
+            get_solution_count(&states[1..], &groups[1..], 0, cache)
+        }
+    } else {
+        get_solution_count(&states[1..], groups, 0, cache)
+    };
+    let result = match states[0] {
+        State::Damaged => {
+            get_solution_count(&states[1..], groups, preceding_damaged + 1, cache)
+        }
+        State::Operational => operational(cache),
+        State::Unknown => {
+            let damaged = get_solution_count(&states[1..], groups, preceding_damaged + 1, cache);
+            damaged.zip(operational(cache)).and_then(|(damaged, operational)| damaged.checked_add(operational))
+        }
+    };
+    cache.insert(key, result);
+    result
+}
+
+/// The condition of each spring, and the sizes of the groups of damaged springs.
+pub type Record = (Vec<State>, Vec<usize>);
+
+/// Parses one `???.### 1,1,3` record per line.
+pub fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+    let parts_regex = Regex::new(r"^(?<records>\S+) (?<groups>(\d+,)*\d+)$").unwrap();
+    let mut records = Vec::<(Vec<State>, Vec<usize>)>::new();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let start = Position::start_of(number);
+        let result = parse::captures(&parts_regex, line, start)?;
+        let groups_match = result.name("groups").unwrap();
+        let groups = aoc_common::numbers::<usize>(groups_match.as_str(), start.at_byte(line, groups_match.start()))?;
+        let states = result["records"].chars().enumerate().map(|(x, c)| {
+            State::from_char(c).ok_or_else(|| ParseError::new(Position::new(number, x + 1), &c.to_string(), "unexpected spring state"))
+        }).collect::<Result<Vec<State>, ParseError>>()?;
+        records.push((states, groups));
+    }
+    Ok(records)
+}
+
+/// Counts the arrangements of a row, if they fit in a `usize`.
+pub fn arrangements(states: &[State], groups: &[usize]) -> Option<usize> {
+    let mut cache: HashMap<(usize, usize, usize), Option<usize>> = HashMap::new();
+    get_solution_count(states, groups, 0, &mut cache)
+}
+
+/// Counts the arrangements of a row unfolded to five copies of it, separated by unknown springs, if they fit in a
+/// `usize`.
+pub fn unfolded_arrangements(states: &[State], groups: &[usize]) -> Option<usize> {
+    let mut unfolded_states: Vec<State> = Vec::new();
+    let mut unfolded_groups: Vec<usize> = Vec::new();
+    for _i in 0..4 {
+        unfolded_states.extend_from_slice(states);
+        unfolded_states.push(State::Unknown);
+        unfolded_groups.extend_from_slice(groups);
+    }
+    unfolded_states.extend_from_slice(states);
+    unfolded_groups.extend_from_slice(groups);
+    arrangements(&unfolded_states, &unfolded_groups)
+}
+
+fn too_many() -> SolveError {
+    SolveError::new("there are too many arrangements to count in 64 bits")
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Model = Vec<Record>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(records: &Vec<Record>) -> Result<usize, SolveError> {
+        let mut solution_count: usize = 0;
+        for (states, groups) in records {
+            let solutions = arrangements(states, groups).ok_or_else(too_many)?;
+            trace!("Found {solutions} solutions");
+            solution_count = solution_count.checked_add(solutions).ok_or_else(too_many)?;
+        }
+        Ok(solution_count)
+    }
+
+    fn part2(records: &Vec<Record>) -> Result<usize, SolveError> {
+        let mut unfolded_count: usize = 0;
+        for (states, groups) in records {
+            let unfolded = unfolded_arrangements(states, groups).ok_or_else(too_many)?;
+            trace!("Found {unfolded} unfolded solutions");
+            unfolded_count = unfolded_count.checked_add(unfolded).ok_or_else(too_many)?;
+        }
+        Ok(unfolded_count)
+    }
+}
+
+/// `size` records of up to 12 springs, each an arrangement with at least one damaged spring and some of its springs
+/// hidden behind `?`. Longer rows could have too many unfolded arrangements to count in 64 bits.
+impl Generator for Day12 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut springs = (0..rng.between(1, 12)).map(|_| rng.chance(0.5)).collect::<Vec<_>>();
+            let damaged = rng.below(springs.len());
+            springs[damaged] = true;
+            let groups = springs.split(|damaged| !damaged).filter(|group| !group.is_empty()).map(|group| group.len().to_string());
+            let groups = groups.collect::<Vec<_>>().join(",");
+            let states = springs.iter().map(|&damaged| if rng.chance(0.4) { '?' } else if damaged { '#' } else { '.' });
+            input += &format!("{} {groups}\n", states.collect::<String>());
+        }
+        input
+    }
+}
@@ -0,0 +1,156 @@
+//! Day 13: Point of Incidence. Finds the lines that mirror patterns of ash and rocks, before and after fixing the
+//! one smudge on each pattern.
+
+use aoc_common::{Generator, Grid, ParseError, Rng, Solution, SolveError};
+use log::trace;
+use std::collections::{HashMap, HashSet};
+
+/// Whether the line after `position` rows mirrors the pattern.
+pub fn is_reflection_row(pattern: &Grid<char>, position: usize) -> bool {
+    (0..position.min(pattern.height() - position)).all(|offset| pattern.row(position - 1 - offset) == pattern.row(position + offset))
+}
+
+/// The number of rows above a line that mirrors the pattern, skipping the line after row `avoid`.
+fn reflection_row(pattern: &Grid<char>, avoid: usize) -> Option<usize> {
+    (1..pattern.height()).find(|&position| position != avoid && is_reflection_row(pattern, position))
+}
+
+/// Summarizes the line of symmetry of the pattern: 100 times the rows above a horizontal one, or the columns left of
+/// a vertical one. `avoid_return` is a summary to skip.
+pub fn get_symmetry(pattern: &Grid<char>, avoid_return: usize) -> usize {
+    let (avoid_row, avoid_column) = if avoid_return >= 100 { (avoid_return / 100, 0) } else { (0, avoid_return) };
+    if let Some(row) = reflection_row(pattern, avoid_row) {
+        return row * 100;
+    }
+    reflection_row(&pattern.transpose(), avoid_column).unwrap_or(0)
+}
+
+/// The summaries of every line that mirrors the pattern.
+pub fn symmetries(pattern: &Grid<char>) -> Vec<usize> {
+    let transposed = pattern.transpose();
+    let rows = (1..pattern.height()).filter(|&row| is_reflection_row(pattern, row)).map(|row| row * 100);
+    rows.chain((1..transposed.height()).filter(|&column| is_reflection_row(&transposed, column))).collect()
+}
+
+fn smudge(original: &Grid<char>, position: (usize, usize)) -> Grid<char> {
+    let mut smudged = original.clone();
+    smudged[position] = if original[position] == '.' { '#' } else { '.' };
+    smudged
+}
+
+/// The summaries of the new lines of symmetry that fixing a single smudge gives.
+pub fn smudged_symmetries(pattern: &Grid<char>) -> HashSet<usize> {
+    let normal_symmetry = get_symmetry(pattern, 0);
+    let mut smudged_solutions = HashSet::<usize>::new();
+    for position in pattern.positions() {
+        let symmetry = get_symmetry(&smudge(pattern, position), normal_symmetry);
+        if symmetry != 0 && symmetry != normal_symmetry {
+            smudged_solutions.insert(symmetry);
+        }
+    }
+    smudged_solutions
+}
+
+/// A random pattern mirrored by one line and with one smudge keeping it from being mirrored by another, or nothing
+/// when the attempt found more lines than that. Both lines are given as summaries.
+fn mirrored_pattern(rng: &mut Rng, width: usize, height: usize) -> Option<Grid<char>> {
+    let mut lines = (1..width).chain((1..height).map(|row| row * 100)).collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+    let (kept, smudged) = (lines[0], lines[1]);
+    let mirror = |line: usize, (x, y): (usize, usize)| {
+        if line >= 100 {
+            (2 * (line / 100) - 1).checked_sub(y).filter(|&y| y < height).map(|y| (x, y))
+        } else {
+            (2 * line - 1).checked_sub(x).filter(|&x| x < width).map(|x| (x, y))
+        }
+    };
+    let positions = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).collect::<Vec<_>>();
+    let mirrored = positions.iter().copied().filter(|&position| mirror(smudged, position).is_some()).collect::<Vec<_>>();
+    let smudge = *rng.choose(&mirrored);
+    let partner = mirror(smudged, smudge).unwrap();
+
+    // cells that either line mirrors onto each other are alike, except for the smudge and its partner
+    fn root(parent: &[usize], mut cell: usize) -> usize {
+        while parent[cell] != cell {
+            cell = parent[cell];
+        }
+        cell
+    }
+    let index = |(x, y): (usize, usize)| y * width + x;
+    let mut parent = (0..width * height).collect::<Vec<_>>();
+    for &position in &positions {
+        for line in [kept, smudged] {
+            let Some(other) = mirror(line, position) else { continue };
+            if line == smudged && (position == smudge || position == partner) {
+                continue;
+            }
+            let (a, b) = (root(&parent, index(position)), root(&parent, index(other)));
+            parent[a] = b;
+        }
+    }
+    let (smudge_root, partner_root) = (root(&parent, index(smudge)), root(&parent, index(partner)));
+    if smudge_root == partner_root {
+        return None;
+    }
+    let mut colors = HashMap::from([(smudge_root, '#'), (partner_root, '.')]);
+    let cells = positions.iter().map(|&position| *colors.entry(root(&parent, index(position))).or_insert_with(|| *rng.choose(&['.', '#'])));
+    let pattern = Grid::new(width, height, cells.collect());
+    (symmetries(&pattern) == [kept] && smudged_symmetries(&pattern) == HashSet::from([smudged])).then_some(pattern)
+}
+
+/// Parses the patterns, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    aoc_common::numbered_blocks(input)
+        .iter()
+        .map(|block| Grid::from_lines(block, |c| matches!(c, '.' | '#').then_some(c)))
+        .collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Model = Vec<Grid<char>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(patterns: &Vec<Grid<char>>) -> Result<usize, SolveError> {
+        // check for symmetry in y-direction
+        Ok(patterns.iter().map(|pattern| get_symmetry(pattern, 0)).sum())
+    }
+
+    fn part2(patterns: &Vec<Grid<char>>) -> Result<usize, SolveError> {
+        let mut smudged_sum = 0;
+        for (number, pattern) in patterns.iter().enumerate() {
+            let smudged_solutions = smudged_symmetries(pattern);
+            if smudged_solutions.is_empty() {
+                return Err(SolveError::new(format!("pattern {} has no new line of symmetry with a smudge fixed", number + 1)));
+            } else if smudged_solutions.len() == 1 {
+                trace!("one smudged solution for {pattern:?}: {smudged_solutions:?}");
+                smudged_sum += smudged_solutions.iter().next().unwrap();
+            } else {
+                return Err(SolveError::new(format!("pattern {} has {} new lines of symmetry with a smudge fixed", number + 1, smudged_solutions.len())));
+            }
+        }
+        Ok(smudged_sum)
+    }
+}
+
+/// `size` patterns of 5 to 17 rows and columns, now and then of a single row, each mirrored by exactly one line and
+/// with exactly one smudge.
+impl Generator for Day13 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut patterns = Vec::<String>::new();
+        while patterns.len() < size {
+            let width = rng.below(13) + 5;
+            let height = if rng.chance(0.1) { 1 } else { rng.below(13) + 5 };
+            if let Some(pattern) = mirrored_pattern(rng, width, height) {
+                patterns.push(pattern.to_string());
+            }
+        }
+        patterns.join("\n")
+    }
+}
@@ -0,0 +1,181 @@
+//! Day 14: Parabolic Reflector Dish. Rolls the rounded rocks on a platform by tilting it, and weighs the load they
+//! put on its north side.
+
+use aoc_common::{cycle, Cell, Color, Direction, Generator, Grid, ParseError, Render, Rng, Simulation, Solution, SolveError};
+use log::{debug, trace};
+
+/// A spot on the platform: empty, a rounded rock that rolls, or a cube-shaped rock that stays.
+#[derive(Eq, PartialEq, Clone, Debug, Hash, Copy)]
+pub enum Tile {
+    Empty,
+    Rolling,
+    Static,
+}
+
+impl Tile {
+    fn from(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Empty),
+            'O' => Some(Tile::Rolling),
+            '#' => Some(Tile::Static),
+            _ => None,
+        }
+    }
+}
+
+/// Rolls every rounded rock north until it hits the edge, a cube-shaped rock or another rounded rock.
+pub fn tilt_north(field: &mut Grid<Tile>) {
+    for x in 0..field.width() {
+        let mut free = 0;
+        for y in 0..field.height() {
+            match field[(x, y)] {
+                Tile::Static => free = y + 1,
+                Tile::Rolling => {
+                    field[(x, y)] = Tile::Empty;
+                    field[(x, free)] = Tile::Rolling;
+                    free += 1;
+                }
+                Tile::Empty => {}
+            }
+        }
+    }
+}
+
+/// The order the platform is tilted in during a spin cycle.
+pub const SPIN_ORDER: [Direction; 4] = [Direction::North, Direction::West, Direction::South, Direction::East];
+
+/// Rolls every rounded rock as far as it goes in `direction`. Turning the platform clockwise brings west north, so
+/// each direction is tilted north after as many turns as it comes after north in a spin cycle.
+pub fn tilt(field: &Grid<Tile>, direction: Direction) -> Grid<Tile> {
+    let turns = SPIN_ORDER.iter().position(|&tilted| tilted == direction).unwrap();
+    let mut field = (0..turns).fold(field.clone(), |field, _| field.rotate_clockwise());
+    tilt_north(&mut field);
+    (0..turns).fold(field, |field, _| field.rotate_counterclockwise())
+}
+
+/// Tilts north, west, south and east. Turning the platform clockwise after each tilt brings the next direction north.
+pub fn spin_cycle(field: &Grid<Tile>) -> Grid<Tile> {
+    let mut field = field.clone();
+    for _ in 0..4 {
+        tilt_north(&mut field);
+        field = field.rotate_clockwise();
+    }
+    field
+}
+
+/// The load on the north side: each rounded rock weighs as much as its number of rows from the south edge.
+pub fn north_tension(field: &Grid<Tile>) -> usize {
+    field.iter().filter(|(_, tile)| **tile == Tile::Rolling).map(|((_, y), _)| field.height() - y).sum()
+}
+
+/// Part 2's number of spin cycles.
+pub const SPIN_CYCLES: usize = 1000000000;
+
+/// The platform after `cycles` spin cycles. It ends up in a loop of states long before a billion of them, which is
+/// found keeping only two platforms in memory.
+pub fn spun(field: &Grid<Tile>, cycles: usize) -> Grid<Tile> {
+    let cycle = cycle::brent(field.clone(), spin_cycle);
+    debug!("cycle start: {} cycle length: {}", cycle.tail, cycle.period);
+    debug!("after {cycles} cycles: {}", cycle.reduce(cycles));
+    cycle.state_at(field.clone(), |field| {
+        let next = spin_cycle(field);
+        trace!("load {}", north_tension(&next));
+        next
+    }, cycles)
+}
+
+pub fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+    Grid::from_str(input, Tile::from)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Model = Grid<Tile>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(field: &Grid<Tile>) -> Result<usize, SolveError> {
+        let mut field = field.clone();
+        debug!("before rolling: {}", north_tension(&field));
+        tilt_north(&mut field);
+        Ok(north_tension(&field))
+    }
+
+    fn part2(field: &Grid<Tile>) -> Result<usize, SolveError> {
+        Ok(north_tension(&spun(field, SPIN_CYCLES)))
+    }
+}
+
+/// The rocks where part 1 tilts them to and where part 2 spins them to.
+impl Render for Day14 {
+    fn render(field: &Grid<Tile>, part: u8) -> Result<Grid<Cell>, SolveError> {
+        let field = if part == 1 {
+            let mut field = field.clone();
+            tilt_north(&mut field);
+            field
+        } else {
+            spun(field, SPIN_CYCLES)
+        };
+        Ok(picture(&field))
+    }
+}
+
+/// The rounded rocks in orange and the cube-shaped rocks in grey.
+fn picture(field: &Grid<Tile>) -> Grid<Cell> {
+    field.map(|tile| match tile {
+        Tile::Empty => Cell::new(' ', Color::GREY),
+        Tile::Rolling => Cell::new('O', Color::ORANGE),
+        Tile::Static => Cell::new('#', Color::GREY),
+    })
+}
+
+/// The platform as the spin cycles tilt it, one tilt per step.
+#[derive(Clone)]
+pub struct Tilting {
+    pub field: Grid<Tile>,
+    pub tilts: usize,
+}
+
+/// Tilts in the order of the spin cycles, forever: the rocks keep going around once they settle into a loop.
+impl Simulation for Day14 {
+    type State = Tilting;
+
+    fn start(input: &str) -> Result<Tilting, ParseError> {
+        Ok(Tilting { field: parse(input)?, tilts: 0 })
+    }
+
+    fn step(state: &Tilting) -> Option<(Tilting, Vec<String>)> {
+        let direction = SPIN_ORDER[state.tilts % 4];
+        let next = Tilting { field: tilt(&state.field, direction), tilts: state.tilts + 1 };
+        let mut events = vec![format!("tilt {direction:?}").to_lowercase()];
+        if next.tilts.is_multiple_of(4) {
+            events.push(format!("spin cycle {} ends with a load of {}", next.tilts / 4, north_tension(&next.field)));
+        }
+        Some((next, events))
+    }
+
+    fn picture(state: &Tilting) -> Grid<Cell> {
+        picture(&state.field)
+    }
+
+    fn details(state: &Tilting) -> Vec<String> {
+        vec![
+            format!("tilts: {}", state.tilts),
+            format!("spin cycles: {}", state.tilts / 4),
+            format!("load on the north side: {}", north_tension(&state.field)),
+        ]
+    }
+}
+
+/// A `size` by `size` platform with rounded rocks on about a fifth of it and cube rocks on a tenth.
+impl Generator for Day14 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let tiles = (0..size * size).map(|_| if rng.chance(0.2) { 'O' } else if rng.chance(0.125) { '#' } else { '.' });
+        Grid::new(size, size, tiles.collect()).to_string()
+    }
+}
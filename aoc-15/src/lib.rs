@@ -0,0 +1,135 @@
+//! Day 15: Lens Library. Hashes the steps of the initialization sequence and follows them to arrange lenses in 256
+//! boxes.
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+
+
+/// A lens in a box, known by its label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lens {
+    pub label: String,
+    /// The focal length.
+    pub strength: usize,
+}
+
+impl Lens {
+    pub fn new(label: String, strength: usize) -> Lens {
+        Lens {
+            label,
+            strength,
+        }
+    }
+}
+
+/// The HASH algorithm: a number in `0..256` for any string.
+pub fn compute_hash(data: &str) -> usize {
+    let mut result = 0;
+    for c in data.chars() {
+        result += c as usize;
+        result *= 17;
+        result %= 256;
+    }
+    result
+}
+
+/// What a step does to the lens with its label: `-` removes it, `=` puts a lens of the given focal length in its place.
+pub enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+pub struct Step {
+    /// The step as written, which part 1 hashes.
+    pub text: String,
+    pub label: String,
+    pub operation: Operation,
+}
+
+/// Parses the comma separated steps on the first line.
+pub fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+    let step_regex = Regex::new(r"^(?<label>[^=-]+)(-|=(?<strength>\d+))$").unwrap();
+    let Some((number, line)) = aoc_common::numbered_lines(input).first().copied() else {
+        return Err(ParseError::new(Position::start_of(1), "", "expected a list of steps"));
+    };
+    let mut steps = Vec::<Step>::new();
+    let mut offset = 0;
+    for text in line.split(",") {
+        let start = Position::start_of(number).at_byte(line, offset);
+        offset += text.len() + 1;
+        let result = parse::captures(&step_regex, text, start)?;
+        let operation = match result.name("strength") {
+            Some(_) => Operation::Insert(parse::parse_group(&result, "strength", text, start)?),
+            None => Operation::Remove,
+        };
+        steps.push(Step { text: text.to_string(), label: result["label"].to_string(), operation });
+    }
+    Ok(steps)
+}
+
+/// The lenses in each of the 256 boxes after following the steps, front to back.
+pub fn arrange(steps: &[Step]) -> Vec<Vec<Lens>> {
+    let mut boxes = (0..256).map(|_| Vec::<Lens>::new()).collect::<Vec<Vec<Lens>>>();
+    for step in steps {
+        let lenses = &mut boxes[compute_hash(&step.label)];
+        match step.operation {
+            Operation::Remove => {
+                lenses.retain(|lens| lens.label != step.label);
+            }
+            Operation::Insert(new_strength) => {
+                if let Some(lens) = lenses.iter_mut().find(|lens| lens.label == step.label) {
+                    lens.strength = new_strength;
+                } else {
+                    lenses.push(Lens::new(step.label.clone(), new_strength));
+                }
+            }
+        }
+    }
+    boxes
+}
+
+/// The sum of the box number times the slot times the focal length of every lens, all counted from 1.
+pub fn focusing_power(boxes: &[Vec<Lens>]) -> usize {
+    boxes.iter().enumerate().map(|(box_index, lenses)|
+        lenses.iter().enumerate().map(|(lens_position, lens)| (1+box_index)*(1+lens_position)*(lens.strength)).sum::<usize>()
+    ).sum::<usize>()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Model = Vec<Step>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(steps: &Vec<Step>) -> Result<usize, SolveError> {
+        Ok(steps.iter().map(|step| compute_hash(&step.text)).sum::<usize>())
+    }
+
+    fn part2(steps: &Vec<Step>) -> Result<usize, SolveError> {
+        let boxes = arrange(steps);
+        trace!("Boxes: {:?}", boxes);
+        Ok(focusing_power(&boxes))
+    }
+}
+
+/// `size` steps on a pool of about `size / 4` labels, so lenses get replaced and removed again.
+impl Generator for Day15 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let labels = (0..size / 4 + 1)
+            .map(|_| {
+                let length = rng.below(6) + 1;
+                rng.word(length)
+            })
+            .collect::<Vec<_>>();
+        let steps = (0..size).map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(0.4) { format!("{label}-") } else { format!("{label}={}", rng.between(1, 9)) }
+        });
+        steps.collect::<Vec<_>>().join(",") + "\n"
+    }
+}
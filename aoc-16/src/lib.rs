@@ -0,0 +1,239 @@
+//! Day 16: The Floor Will Be Lava. Follows a beam of light through a contraption of mirrors and splitters and counts
+//! the tiles it energizes.
+
+use aoc_common::{Cell, Color, Direction, Generator, Grid, ParseError, Render, Rng, Simulation, Solution, SolveError};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// A tile of the contraption. `\` is `MirrorSE` and `/` is `MirrorNE`, after the way their right ends point.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,
+    SplitterHorizontal,
+    SplitterVertical,
+    MirrorSE,
+    MirrorNE,
+}
+
+impl Tile {
+    fn from(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Empty),
+            '|' => Some(Tile::SplitterVertical),
+            '-' => Some(Tile::SplitterHorizontal),
+            '/' => Some(Tile::MirrorNE),
+            '\\' => Some(Tile::MirrorSE),
+            _ => None,
+        }
+    }
+}
+
+/// The directions a beam going in `direction` leaves a tile in.
+pub fn get_next_directions(tile: &Tile, direction: Direction) -> Vec<Direction> {
+    match tile {
+        Tile::Empty => {
+            vec!(direction)
+        },
+        Tile::SplitterHorizontal => {
+            match direction {
+                Direction::North | Direction::South => {
+                    vec!(Direction::East, Direction::West)
+                },
+                Direction::East | Direction::West => {
+                    vec!(direction)
+                }
+            }
+        },
+        Tile::SplitterVertical => {
+            match direction {
+                Direction::East | Direction::West => vec!(Direction::North, Direction::South),
+                Direction::North | Direction::South => vec!(direction),
+            }
+        },
+        Tile::MirrorSE => match direction {
+                Direction::North => vec!(Direction::West),
+                Direction::East => vec!(Direction::South),
+                Direction::South => vec!(Direction::East),
+                Direction::West => vec!(Direction::North),
+            },
+        Tile::MirrorNE => match direction {
+                Direction::North => vec!(Direction::East),
+                Direction::East => vec!(Direction::North),
+                Direction::South => vec!(Direction::West),
+                Direction::West => vec!(Direction::South),
+            },
+    }
+}
+
+/// Beams going through the contraption, one tile at a time.
+#[derive(Clone, Debug)]
+pub struct Beams {
+    /// The tiles the beams are about to enter, and their direction.
+    pub heads: Vec<(isize, isize, Direction)>,
+    /// Every tile a beam went through in every direction, a beam that gets there again repeats what it did.
+    pub past: HashSet<(isize, isize, Direction)>,
+    pub energized: HashSet<(isize, isize)>,
+}
+
+impl Beams {
+    pub fn new(start: (isize, isize, Direction)) -> Self {
+        Beams { heads: vec![start], past: HashSet::new(), energized: HashSet::new() }
+    }
+
+    /// Moves every beam through the tile it is entering, dropping the beams that leave the contraption or repeat
+    /// another.
+    pub fn advance(&mut self, field: &Grid<Tile>) {
+        let mut next_beams = Vec::<(isize, isize, Direction)>::new();
+        for beam in std::mem::take(&mut self.heads) {
+            if !self.past.insert(beam) {
+                continue;
+            }
+            let (x, y, direction) = beam;
+            self.energized.insert((x, y));
+            for direction in get_next_directions(&field[(x as usize, y as usize)], direction) {
+                let (next_x, next_y) = direction.offset((x, y));
+                if field.contains((next_x, next_y)) {
+                    next_beams.push((next_x, next_y, direction));
+                }
+            }
+        }
+        self.heads = next_beams;
+    }
+}
+
+/// The tiles a beam entering the contraption at `start` passes, going in the given direction.
+pub fn energized_tiles(field: &Grid<Tile>, start: (isize, isize, Direction)) -> HashSet<(isize, isize)> {
+    let mut beams = Beams::new(start);
+    while !beams.heads.is_empty() {
+        beams.advance(field);
+    }
+    beams.energized
+}
+
+/// The number of tiles a beam entering the contraption at `start` passes, going in the given direction.
+pub fn get_energized_tiles(field: &Grid<Tile>, start: (isize, isize, Direction)) -> usize {
+    energized_tiles(field, start).len()
+}
+
+pub fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+    Grid::from_str(input, Tile::from)
+}
+
+/// Every way a beam can enter the contraption from an edge tile, starting with part 1's.
+pub fn edge_starts(field: &Grid<Tile>) -> Vec<(isize, isize, Direction)> {
+    let mut starts = vec![(0, 0, Direction::East)];
+    for x in 0..field.width() as isize {
+        starts.push((x, 0, Direction::South));
+        starts.push((x, field.height() as isize - 1, Direction::North));
+    }
+    for y in 0..field.height() as isize {
+        starts.push((0, y, Direction::East));
+        starts.push((field.width() as isize - 1, y, Direction::West));
+    }
+    starts
+}
+
+/// The most tiles a beam entering from any edge tile energizes.
+pub fn most_energized_tiles(field: &Grid<Tile>) -> usize {
+    edge_starts(field).into_iter().map(|start| get_energized_tiles(field, start)).max().unwrap()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Model = Grid<Tile>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(field: &Grid<Tile>) -> Result<usize, SolveError> {
+        Ok(get_energized_tiles(field, (0, 0, Direction::East)))
+    }
+
+    fn part2(field: &Grid<Tile>) -> Result<usize, SolveError> {
+        Ok(most_energized_tiles(field))
+    }
+}
+
+/// The tiles energized by the beam of part 1, or by the beam that energizes the most.
+impl Render for Day16 {
+    fn render(field: &Grid<Tile>, part: u8) -> Result<Grid<Cell>, SolveError> {
+        let start = if part == 1 {
+            (0, 0, Direction::East)
+        } else {
+            edge_starts(field).into_iter().max_by_key(|start| get_energized_tiles(field, *start)).unwrap()
+        };
+        Ok(contraption(field, &energized_tiles(field, start)))
+    }
+}
+
+/// The beam of part 1 and the contraption it goes through.
+#[derive(Clone)]
+pub struct Lighting {
+    pub field: Rc<Grid<Tile>>,
+    pub beams: Beams,
+}
+
+/// Moves the beams of part 1 a tile per step, until every beam has left or repeats another.
+impl Simulation for Day16 {
+    type State = Lighting;
+
+    fn start(input: &str) -> Result<Lighting, ParseError> {
+        Ok(Lighting { field: Rc::new(parse(input)?), beams: Beams::new((0, 0, Direction::East)) })
+    }
+
+    fn step(state: &Lighting) -> Option<(Lighting, Vec<String>)> {
+        if state.beams.heads.is_empty() {
+            return None;
+        }
+        let mut next = state.clone();
+        next.beams.advance(&next.field);
+        let mut energized = next.beams.energized.difference(&state.beams.energized).copied().collect::<Vec<_>>();
+        energized.sort_by_key(|&(x, y)| (y, x));
+        let mut events = energized.into_iter().map(|(x, y)| format!("energizes {x},{y}")).collect::<Vec<_>>();
+        for &(x, y, direction) in &state.beams.heads {
+            if !state.beams.past.contains(&(x, y, direction)) && get_next_directions(&state.field[(x as usize, y as usize)], direction).len() == 2 {
+                events.push(format!("splits at {x},{y}"));
+            }
+        }
+        Some((next, events))
+    }
+
+    fn picture(state: &Lighting) -> Grid<Cell> {
+        let mut picture = contraption(&state.field, &state.beams.energized);
+        for &(x, y, direction) in &state.beams.heads {
+            picture[(x as usize, y as usize)] = Cell::new(direction.arrow(), Color::WHITE).on(Color::RED);
+        }
+        picture
+    }
+
+    fn details(state: &Lighting) -> Vec<String> {
+        vec![format!("energized tiles: {}", state.beams.energized.len()), format!("beams: {}", state.beams.heads.len())]
+    }
+}
+
+/// The contraption with the energized tiles in orange.
+fn contraption(field: &Grid<Tile>, energized: &HashSet<(isize, isize)>) -> Grid<Cell> {
+    let mut picture = field.map(|tile| match tile {
+        Tile::Empty => Cell::new(' ', Color::GREY),
+        Tile::SplitterHorizontal => Cell::new('-', Color::WHITE),
+        Tile::SplitterVertical => Cell::new('|', Color::WHITE),
+        Tile::MirrorSE => Cell::new('\\', Color::WHITE),
+        Tile::MirrorNE => Cell::new('/', Color::WHITE),
+    });
+    for &(x, y) in energized {
+        picture[(x as usize, y as usize)].background = Color::ORANGE;
+    }
+    picture
+}
+
+/// A `size` by `size` contraption with a mirror or splitter on about one in ten tiles.
+impl Generator for Day16 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let tiles = (0..size * size).map(|_| if rng.chance(0.1) { *rng.choose(&['|', '-', '/', '\\']) } else { '.' });
+        Grid::new(size, size, tiles.collect()).to_string()
+    }
+}
@@ -0,0 +1,101 @@
+//! Day 17: Clumsy Crucible. Finds the route from the top left to the bottom right of a city that loses the least
+//! heat, for crucibles that can only go so far in a straight line.
+
+use aoc_common::{search, Axis, Cell, Color, Direction, Generator, Grid, ParseError, Render, Rng, Solution, SolveError};
+use log::trace;
+
+/// The least heat lost on the way to the bottom right, when the crucible moves at least `min_steps` and at most
+/// `max_steps` in a straight line before it has to turn. A state is a block together with the axis the crucible
+/// arrived along, since it has to continue along the other one. `None` if the crucible cannot get there.
+pub fn find_best_path(field: &Grid<usize>, min_steps: usize, max_steps: usize) -> Option<usize> {
+    best_route(field, min_steps, max_steps).map(|(cost, _)| cost)
+}
+
+/// Like `find_best_path`, together with every block of the route from the top left to the bottom right.
+pub fn best_route(field: &Grid<usize>, min_steps: usize, max_steps: usize) -> Option<(usize, Vec<(usize, usize)>)> {
+    let goal = (field.width() - 1, field.height() - 1);
+    let starts = [((0, 0), Axis::Horizontal), ((0, 0), Axis::Vertical)];
+    let neighbours = |&(position, axis): &((usize, usize), Axis)| {
+        let mut moves = Vec::<(((usize, usize), Axis), usize)>::new();
+        for direction in Direction::ALL.into_iter().filter(|direction| direction.axis() != axis) {
+            let mut current = position;
+            let mut cost = 0;
+            for steps in 1..=max_steps {
+                let Some(next) = field.step(current, direction) else {
+                    break;
+                };
+                current = next;
+                cost += field[current];
+                if steps >= min_steps {
+                    moves.push(((current, direction.axis()), cost));
+                }
+            }
+        }
+        moves
+    };
+    let path = search::dijkstra(starts, neighbours, |(position, _)| *position == goal)?;
+    trace!("path: {:?}", path.states);
+    // the states are where the crucible turns, it goes straight from one to the next
+    let toward = |from: usize, to: usize| if from < to { from + 1 } else if from > to { from - 1 } else { from };
+    let mut blocks = vec![(0, 0)];
+    for &((x, y), _) in &path.states[1..] {
+        while let Some(&(from_x, from_y)) = blocks.last().filter(|&&block| block != (x, y)) {
+            blocks.push((toward(from_x, x), toward(from_y, y)));
+        }
+    }
+    Some((path.cost, blocks))
+}
+
+/// Parses the heat loss digit of every block.
+pub fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::from_str(input, |c| c.to_digit(10).map(|digit| digit as usize))
+}
+
+fn no_path() -> SolveError {
+    SolveError::new("the crucible cannot reach the bottom right")
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Model = Grid<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(field: &Grid<usize>) -> Result<usize, SolveError> {
+        find_best_path(field, 1, 3).ok_or_else(no_path)
+    }
+
+    fn part2(field: &Grid<usize>) -> Result<usize, SolveError> {
+        find_best_path(field, 4, 10).ok_or_else(no_path)
+    }
+}
+
+/// The heat losses, darker where less heat is lost, with the route of the part's crucible in yellow.
+impl Render for Day17 {
+    fn render(field: &Grid<usize>, part: u8) -> Result<Grid<Cell>, SolveError> {
+        let (min_steps, max_steps) = if part == 1 { (1, 3) } else { (4, 10) };
+        let (_, route) = best_route(field, min_steps, max_steps).ok_or_else(no_path)?;
+        let mut picture = field.map(|&loss| {
+            let shade = (loss.min(9) * 20) as u8;
+            Cell::new(char::from_digit(loss as u32 % 10, 10).unwrap(), Color::GREY).on(Color(shade, shade / 2, shade / 3))
+        });
+        for block in route {
+            picture[block] = Cell::new(picture[block].glyph, Color::BLACK).on(Color::YELLOW);
+        }
+        Ok(picture)
+    }
+}
+
+/// A `size` by `size` map of heat losses from 1 to 9, at least 5 by 5 so the ultra crucible can reach the end.
+impl Generator for Day17 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(5);
+        let losses = (0..size * size).map(|_| rng.between(1, 9));
+        Grid::new(size, size, losses.collect()).to_string()
+    }
+}
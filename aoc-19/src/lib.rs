@@ -0,0 +1,343 @@
+//! Day 19: Aplenty. Sends machine parts through workflows of rules on their ratings, and counts every combination
+//! of ratings that would be accepted.
+
+use aoc_common::{parse, BoxSet, Check, Generator, HyperRect, Interval, Oracle, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Comparison {
+    Greater, Less
+}
+
+impl Comparison {
+    fn from(c: char) -> Option<Comparison> {
+        match c {
+            '<' => Some(Comparison::Less),
+            '>' => Some(Comparison::Greater),
+            _ => None,
+        }
+    }
+}
+
+/// One of the four ratings of a part.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Parameter {
+    X, M, A, S
+}
+
+const PARAMETERS: [Parameter; 4] = [Parameter::X, Parameter::M, Parameter::A, Parameter::S];
+
+impl Parameter {
+    fn from(c: char) -> Option<Parameter> {
+        match c {
+            'x' => Some(Parameter::X),
+            'm' => Some(Parameter::M),
+            'a' => Some(Parameter::A),
+            's' => Some(Parameter::S),
+            _ => None,
+        }
+    }
+
+    /// The dimension of the parameter in a box of ratings.
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Reads the single character parameter matched by `group` in `line`.
+    fn parse(line: &str, group: regex::Match, start: Position) -> Result<Parameter, ParseError> {
+        let position = start.at_byte(line, group.start());
+        Parameter::from(group.as_str().chars().next().unwrap()).ok_or_else(|| ParseError::new(position, group.as_str(), "expected x, m, a or s"))
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub struct Workflow {
+    /// Sends a part whose rating compares as given with the value to the named workflow, or to `A` or `R`.
+    pub rules: Vec<(Parameter, Comparison, usize, String)>,
+    /// Where a part goes when no rule sends it on.
+    pub default_destination: String,
+}
+
+/// The ratings of a part.
+pub type Part = HashMap<Parameter, usize>;
+
+impl Workflow {
+    fn new(line: &str, start: Position) -> Result<(String, Workflow), ParseError> {
+        let regex = Regex::new(r"^(?<name>\w+)\{(?<groups>(\w(<|>)\d+:\w+,)*)(?<default_destination>\w+)\}$").unwrap();
+        let result = parse::captures(&regex, line, start)?;
+        let regex = Regex::new(r"(?<parameter>\w)(?<comparison>(<|>))(?<value>\d+):(?<target>\w+),").unwrap();
+        let mut rules = Vec::<(Parameter, Comparison, usize, String)>::new();
+        for rule in regex.captures_iter(line) {
+            let parameter = Parameter::parse(line, rule.name("parameter").unwrap(), start)?;
+            let comparison = Comparison::from(rule["comparison"].chars().next().unwrap()).unwrap();
+            let value = parse::parse_group(&rule, "value", line, start)?;
+            let target = &rule["target"];
+            rules.push((parameter, comparison, value, target.to_string()));
+        }
+        Ok((result["name"].to_string(), Workflow {
+            rules,
+            default_destination: result["default_destination"].to_string(),
+        }))
+    }
+
+    /// Where the workflow sends the part.
+    pub fn process(self: &Workflow, part: &Part) -> &String {
+        for (parameter, comparison, value, result) in self.rules.iter() {
+            let part_value = part[parameter];
+            if *comparison == Comparison::Greater && part_value > *value {
+                return result;
+            }
+            if *comparison == Comparison::Less && part_value < *value {
+                return result;
+            }
+        }
+        &self.default_destination
+    }
+}
+
+/// Sends every combination of ratings in `ratings` through the workflows starting at `name`, collecting the
+/// accepted ones. Each rule splits the box in the part it sends on and the part that continues with the next rule.
+pub fn accept_ratings(workflows: &HashMap<String, Workflow>, name: &str, ratings: HyperRect<4>, accepted: &mut BoxSet<4>) {
+    if name == "A" {
+        accepted.insert(ratings);
+        return;
+    }
+    if name == "R" {
+        return;
+    }
+    let workflow = &workflows[name];
+    let mut rest = Some(ratings);
+    for (parameter, comparison, value, target) in &workflow.rules {
+        let Some(current) = rest else {
+            return;
+        };
+        let (matching, other) = match comparison {
+            // a value past the ratings compares like the edge of the ratings
+            Comparison::Less => current.split_at(parameter.index(), (*value).min(4001) as i64),
+            Comparison::Greater => {
+                let (below, above) = current.split_at(parameter.index(), (*value).min(4000) as i64 + 1);
+                (above, below)
+            }
+        };
+        if let Some(matching) = matching {
+            trace!("{name}: {matching:?} -> {target}");
+            accept_ratings(workflows, target, matching, accepted);
+        }
+        rest = other;
+    }
+    if let Some(rest) = rest {
+        accept_ratings(workflows, &workflow.default_destination, rest, accepted);
+    }
+}
+
+/// Whether the workflows starting at `in` accept the part.
+pub fn accepts(workflows: &HashMap<String, Workflow>, part: &Part) -> bool {
+    let mut current_workflow = "in";
+    while !(current_workflow == "A" || current_workflow == "R") {
+        current_workflow = workflows[current_workflow].process(part);
+    }
+    current_workflow == "A"
+}
+
+/// Counts the accepted combinations of ratings the slow but simple way. The values rules compare against cut the
+/// ratings into boxes whose parts all go the same way, so sending one part of each box through the workflows tells
+/// about all of them.
+pub fn accepted_by_boxes(workflows: &HashMap<String, Workflow>) -> usize {
+    let mut cuts = [(); 4].map(|_| vec![1, 4001]);
+    for workflow in workflows.values() {
+        for (parameter, comparison, value, _) in &workflow.rules {
+            cuts[parameter.index()].push(match comparison {
+                Comparison::Less => *value,
+                Comparison::Greater => value.saturating_add(1),
+            });
+        }
+    }
+    for cuts in &mut cuts {
+        cuts.retain(|cut| (1..=4001).contains(cut));
+        cuts.sort();
+        cuts.dedup();
+    }
+    let mut accepted = 0;
+    for x in cuts[0].windows(2) {
+        for m in cuts[1].windows(2) {
+            for a in cuts[2].windows(2) {
+                for s in cuts[3].windows(2) {
+                    let part = Part::from([(Parameter::X, x[0]), (Parameter::M, m[0]), (Parameter::A, a[0]), (Parameter::S, s[0])]);
+                    if accepts(workflows, &part) {
+                        accepted += (x[1] - x[0]) * (m[1] - m[0]) * (a[1] - a[0]) * (s[1] - s[0]);
+                    }
+                }
+            }
+        }
+    }
+    accepted
+}
+
+pub struct System {
+    /// The workflows by name.
+    pub workflows: HashMap<String, Workflow>,
+    pub parts: Vec<Part>,
+}
+
+pub fn parse(input: &str) -> Result<System, ParseError> {
+    let blocks = aoc_common::numbered_blocks(input);
+    let [workflow_lines, part_lines] = &blocks[..] else {
+        return Err(ParseError::new(Position::start_of(1), input.lines().next().unwrap_or(""), "expected a block of workflows and a block of parts"));
+    };
+
+    let mut workflows = HashMap::<String, Workflow>::new();
+    let mut lines = HashMap::<String, Position>::new();
+    for (number, line) in workflow_lines {
+        let (name, workflow) = Workflow::new(line, Position::start_of(*number))?;
+        if workflows.contains_key(&name) {
+            return Err(ParseError::new(Position::start_of(*number), &name, "the workflow is already defined"));
+        }
+        lines.insert(name.clone(), Position::start_of(*number));
+        workflows.insert(name, workflow);
+    }
+    check_workflows(&workflows, &lines)?;
+    let inner_regex = Regex::new(r"^\{(?<content>.*)\}$").unwrap();
+    let parameter_regex = Regex::new(r"(?<parameter>\w)=(?<value>\d+)").unwrap();
+    let mut parts = Vec::<Part>::new();
+    for (number, line) in part_lines {
+        let start = Position::start_of(*number);
+        let mut part: Part = HashMap::new();
+        parse::captures(&inner_regex, line, start)?;
+        for result in parameter_regex.captures_iter(line) {
+            part.insert(Parameter::parse(line, result.name("parameter").unwrap(), start)?, parse::parse_group(&result, "value", line, start)?);
+        }
+        if part.len() != PARAMETERS.len() {
+            return Err(ParseError::new(start, line, "expected a value for each of x, m, a and s"));
+        }
+        parts.push(part);
+    }
+    Ok(System { workflows, parts })
+}
+
+/// Makes sure the workflows start at `in`, only send parts to workflows that exist, and never send a part back to a
+/// workflow it went through, so every part ends up accepted or rejected. `lines` are where the workflows are defined.
+fn check_workflows(workflows: &HashMap<String, Workflow>, lines: &HashMap<String, Position>) -> Result<(), ParseError> {
+    let first_line = lines.values().min_by_key(|position| position.line).copied().unwrap_or(Position::start_of(1));
+    if !workflows.contains_key("in") {
+        return Err(ParseError::new(first_line, "", "expected a workflow named in"));
+    }
+    let targets = |workflow: &Workflow| -> Vec<String> {
+        workflow.rules.iter().map(|rule| rule.3.clone()).chain([workflow.default_destination.clone()]).collect()
+    };
+    for (name, workflow) in workflows {
+        if let Some(target) = targets(workflow).into_iter().find(|target| target != "A" && target != "R" && !workflows.contains_key(target)) {
+            return Err(ParseError::new(lines[name], &target, "no workflow has this name"));
+        }
+    }
+    // depth first, a workflow that is still being visited when it comes up again closes a circle
+    let mut finished = HashSet::<&str>::new();
+    for start in workflows.keys() {
+        let mut visiting = vec![(start.as_str(), targets(&workflows[start]))];
+        let mut on_path = HashSet::from([start.as_str()]);
+        while let Some((name, pending)) = visiting.last_mut() {
+            let Some(target) = pending.pop() else {
+                finished.insert(name);
+                on_path.remove(name);
+                visiting.pop();
+                continue;
+            };
+            let Some((target, workflow)) = workflows.get_key_value(&target) else {
+                continue;
+            };
+            if on_path.contains(target.as_str()) {
+                return Err(ParseError::new(lines[target], target, "the workflows send parts around in a circle through this one"));
+            }
+            if !finished.contains(target.as_str()) {
+                on_path.insert(target);
+                visiting.push((target, targets(workflow)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The number of combinations of ratings from 1 to 4000 that the workflows accept.
+pub fn accepted_combinations(workflows: &HashMap<String, Workflow>) -> usize {
+    let mut accepted = BoxSet::<4>::new();
+    let all_ratings = HyperRect::new([Interval::inclusive(1, 4000); 4]);
+    accept_ratings(workflows, "in", all_ratings, &mut accepted);
+    accepted.volume() as usize
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Model = System;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<System, ParseError> {
+        parse(input)
+    }
+
+    fn part1(system: &System) -> Result<usize, SolveError> {
+        let System { workflows, parts } = system;
+        let mut sum: usize = 0;
+        for part in parts {
+            if accepts(workflows, part) {
+                trace!("accepted part {part:?}");
+                sum = part.values().try_fold(sum, |sum, value| sum.checked_add(*value))
+                    .ok_or_else(|| SolveError::new("the sum of the ratings overflows"))?;
+            } else {
+                trace!("rejected part {part:?}");
+            }
+        }
+        Ok(sum)
+    }
+
+    fn part2(system: &System) -> Result<usize, SolveError> {
+        Ok(accepted_combinations(&system.workflows))
+    }
+}
+
+impl Oracle for Day19 {
+    fn checks(system: &System) -> Vec<Check> {
+        vec![Check::new("part 2", Self::part2(system), accepted_by_boxes(&system.workflows))]
+    }
+}
+
+/// About `size` workflows and `size` parts. The workflows form a tree below `in`, so every part ends up accepted or
+/// rejected.
+impl Generator for Day19 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut names = std::collections::HashSet::from(["in".to_string()]);
+        let mut pending = vec!["in".to_string()];
+        let mut workflows = Vec::<String>::new();
+        while let Some(name) = pending.pop() {
+            let mut target = |rng: &mut Rng| {
+                if names.len() < size && rng.chance(0.5) {
+                    loop {
+                        let length = rng.below(2) + 2;
+                        let name = rng.word(length);
+                        if names.insert(name.clone()) {
+                            pending.push(name.clone());
+                            break name;
+                        }
+                    }
+                } else {
+                    rng.choose(&["A", "R"]).to_string()
+                }
+            };
+            let mut workflow = format!("{name}{{");
+            for _ in 0..rng.between(1, 4) {
+                let (parameter, comparison, value) = (*rng.choose(&['x', 'm', 'a', 's']), *rng.choose(&['<', '>']), rng.between(1, 4000));
+                workflow += &format!("{parameter}{comparison}{value}:{},", target(rng));
+            }
+            workflow += &format!("{}}}", target(rng));
+            workflows.push(workflow);
+        }
+        rng.shuffle(&mut workflows);
+        let parts = (0..size).map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.between(1, 4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        });
+        format!("{}\n\n{}\n", workflows.join("\n"), parts.collect::<Vec<_>>().join("\n"))
+    }
+}
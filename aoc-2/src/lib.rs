@@ -0,0 +1,93 @@
+//! Day 2: Cube Conundrum. Finds out how many cubes of each color a bag must hold for the games played with it.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+
+/// A game, with the fewest cubes of each color that make all of its rounds possible.
+pub struct Game {
+    pub id: i32,
+    pub min_red: i32,
+    pub min_green: i32,
+    pub min_blue: i32,
+}
+
+/// Parses one `Game <id>: <count> <color>, ...; ...` line per game.
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let game: Regex = Regex::new(r"^Game (?<id>\d+): (?<data>.*)$").unwrap();
+    let values: Regex = Regex::new(r"(?<value>\d+) (?<color>\w+)").unwrap();
+    let mut games = Vec::<Game>::new();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let start = Position::start_of(number);
+        let game_result = parse::captures(&game, line, start)?;
+        let mut min_red = 0;
+        let mut min_green = 0;
+        let mut min_blue = 0;
+        for value_result in values.captures_iter(line) {
+            let value = parse::parse_group::<i32>(&value_result, "value", line, start)?;
+            let color = value_result.name("color").unwrap();
+            let minimum = match color.as_str() {
+                "red" => &mut min_red,
+                "green" => &mut min_green,
+                "blue" => &mut min_blue,
+                _ => return Err(ParseError::new(start.at_byte(line, color.start()), color.as_str(), "unknown color")),
+            };
+            *minimum = value.max(*minimum);
+        }
+        trace!("Game with id {}: {} => red: {}, green: {}, blue: {}", &game_result["id"], &game_result["data"], min_red, min_green, min_blue);
+        games.push(Game { id: parse::parse_group(&game_result, "id", line, start)?, min_red, min_green, min_blue });
+    }
+    Ok(games)
+}
+
+/// Sums the ids of the games that are possible with 12 red, 13 green and 14 blue cubes.
+pub fn possible_id_sum(games: &[Game]) -> i32 {
+    games.iter()
+        .filter(|game| game.min_red <= 12 && game.min_green <= 13 && game.min_blue <= 14)
+        .map(|game| game.id)
+        .sum()
+}
+
+/// Sums the power, the product of the fewest cubes of each color, of every game.
+pub fn power_sum(games: &[Game]) -> i32 {
+    games.iter().map(|game| game.min_red * game.min_green * game.min_blue).sum()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Model = Vec<Game>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(games: &Vec<Game>) -> Result<i32, SolveError> {
+        Ok(possible_id_sum(games))
+    }
+
+    fn part2(games: &Vec<Game>) -> Result<i32, SolveError> {
+        Ok(power_sum(games))
+    }
+}
+
+/// `size` games of up to six rounds each.
+impl Generator for Day2 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for id in 1..=size {
+            let rounds = (0..rng.between(1, 6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    let count = rng.below(3) + 1;
+                    colors[..count].iter().map(|color| format!("{} {color}", rng.between(1, 20))).collect::<Vec<_>>().join(", ")
+                })
+                .collect::<Vec<_>>();
+            input += &format!("Game {id}: {}\n", rounds.join("; "));
+        }
+        input
+    }
+}
@@ -0,0 +1,558 @@
+//! Day 20: Pulse Propagation. Pushes the button of a network of flip-flops and conjunctions, counting the pulses
+//! sent, and finds the first push that sends a low pulse to `rx`.
+
+use aoc_common::cycle::{self, Occurrences};
+use aoc_common::render;
+use aoc_common::{math, parse, Cell, Color, Generator, Graph, Grid, ParseError, Position, Rng, Simulation, Solution, SolveError};
+use log::{debug, trace};
+use std::collections::{HashMap, VecDeque};
+use regex::Regex;
+use std::option::Option;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    High, Low
+}
+
+impl Pulse {
+    /// 0 for a low pulse and 1 for a high one.
+    pub fn index(&self) -> usize {
+        match self {
+            Pulse::High => 1,
+            Pulse::Low => 0
+        }
+    }
+}
+
+/// A module of the network: a flip-flop, a conjunction, the broadcaster, or one that just receives pulses.
+pub trait Module: Debug {
+    /// Receives a pulse from the module `from`, and returns the pulse it sends to all of its destinations, if any.
+    fn process(&mut self, pulse: Pulse, from: &str) -> Option<Pulse>;
+    /// Tells the module that `from` sends it pulses, which a conjunction remembers one pulse for.
+    fn register_input(&mut self, from: &str);
+    fn clone_box(&self) -> Box<dyn Module>;
+    /// Everything the module remembers between pulses.
+    fn memory(&self) -> Vec<bool>;
+    /// What the module's name starts with in the input, `%` for a flip-flop and `&` for a conjunction.
+    fn prefix(&self) -> &'static str;
+}
+
+impl Clone for Box<dyn Module> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FlipFlopModule {
+    state: bool,
+}
+
+impl FlipFlopModule {
+    fn new() -> Self {
+        FlipFlopModule {
+            state: false,
+        }
+    }
+}
+
+impl Module for FlipFlopModule {
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn prefix(&self) -> &'static str {
+        "%"
+    }
+
+    fn register_input(&mut self, _from: &str) {
+    }
+
+    fn memory(&self) -> Vec<bool> {
+        vec![self.state]
+    }
+
+    fn process(&mut self, pulse: Pulse, _from: &str) -> Option<Pulse> {
+        if pulse == Pulse::High {
+            return None;
+        }
+        self.state = !self.state;
+        if self.state {
+            return Some(Pulse::High);
+        }
+        Some(Pulse::Low)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConjunctionModule {
+    sources: HashMap<String, Pulse>,
+}
+
+impl ConjunctionModule {
+    fn new() -> Self {
+        ConjunctionModule {
+            sources: HashMap::new(),
+        }
+    }
+}
+
+impl Module for ConjunctionModule {
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn prefix(&self) -> &'static str {
+        "&"
+    }
+
+    fn register_input(&mut self, from: &str) {
+        self.sources.insert(from.to_string(), Pulse::Low);
+    }
+
+    fn memory(&self) -> Vec<bool> {
+        let mut sources = self.sources.iter().collect::<Vec<_>>();
+        sources.sort_by_key(|(name, _)| *name);
+        sources.into_iter().map(|(_, pulse)| *pulse == Pulse::High).collect()
+    }
+
+    fn process(&mut self, pulse: Pulse, from: &str) -> Option<Pulse> {
+        self.sources.insert(from.to_string(), pulse);
+        for (_to, pulse) in self.sources.iter() {
+            if *pulse != Pulse::High {
+                return Some(Pulse::High);
+            }
+        }
+        Some(Pulse::Low)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SourceModule {
+}
+
+impl SourceModule {
+    fn new() -> Self {
+        SourceModule {
+        }
+    }
+}
+
+impl Module for SourceModule {
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn prefix(&self) -> &'static str {
+        ""
+    }
+
+    fn register_input(&mut self, _from: &str) {
+    }
+
+    fn memory(&self) -> Vec<bool> {
+        Vec::new()
+    }
+
+    fn process(&mut self, pulse: Pulse, _from: &str) -> Option<Pulse> {
+        Some(pulse)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DefaultModule {
+}
+
+impl DefaultModule {
+    fn new() -> Self {
+        DefaultModule {
+        }
+    }
+}
+
+impl Module for DefaultModule {
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn prefix(&self) -> &'static str {
+        ""
+    }
+
+    fn register_input(&mut self, _from: &str) {
+    }
+
+    fn memory(&self) -> Vec<bool> {
+        Vec::new()
+    }
+
+    fn process(&mut self, _pulse: Pulse, _from: &str) -> Option<Pulse> {
+        None
+    }
+}
+
+/// Hands a pulse sent from one module to another to the receiving module, and returns the pulses it sends in turn,
+/// in the order of its destinations. Pulses to modules that are not in the network go nowhere.
+pub fn deliver(modules: &mut HashMap<String, Box<dyn Module>>, wiring: &Graph, (from, to, pulse): &(String, String, Pulse)) -> Vec<(String, String, Pulse)> {
+    let Some(out_pulse) = modules.get_mut(to).and_then(|module| module.process(*pulse, from)) else {
+        return Vec::new();
+    };
+    wiring.neighbours(wiring.id(to).unwrap()).iter()
+        .map(|&(destination, ())| (to.clone(), wiring.name(destination).to_string(), out_pulse))
+        .collect()
+}
+
+/// Pushes the button once, and returns the number of low and high pulses sent. Every high pulse sent by a module in
+/// `notify` adds press number `i` to its entry in `deps`. Modules can keep sending each other pulses forever, so the
+/// push is given up on with `None` after `MAX_PULSES`.
+pub fn get_counts(modules: &mut HashMap<String, Box<dyn Module>>, wiring: &Graph, notify: &[String], i: usize, deps: &mut HashMap<String, Vec<usize>>) -> Option<(usize, usize)> {
+    let mut counts = [0, 0];
+    let mut current_pulses: VecDeque<(String, String, Pulse)> = VecDeque::from([("button".to_string(), "broadcaster".to_string(), Pulse::Low)]);
+    while let Some(sent) = current_pulses.pop_front() {
+        if counts[0] + counts[1] == MAX_PULSES {
+            return None;
+        }
+        let (from, to, pulse) = &sent;
+        if notify.contains(from) && *pulse == Pulse::High {
+            let presses = deps.entry(from.clone()).or_default();
+            if presses.last() != Some(&i) {
+                presses.push(i);
+            }
+            trace!("{from} -{pulse:?} -> {to} ({i})");
+        }
+        counts[pulse.index()] += 1;
+        current_pulses.extend(deliver(modules, wiring, &sent));
+    }
+    Some((counts[0], counts[1]))
+}
+
+/// The part of the network upstream of `output`, after some number of presses, and whether `output` sent a high
+/// pulse during the last one. Nothing else reaches these modules, so they go through presses on their own.
+#[derive(Clone)]
+struct Subnetwork {
+    output: String,
+    modules: HashMap<String, Box<dyn Module>>,
+    sent_high: bool,
+}
+
+impl Subnetwork {
+    fn new(network: &Network, output: &str) -> Self {
+        let upstream = network.wiring.reversed().breadth_first(network.wiring.id(output).unwrap());
+        let modules = upstream.into_iter()
+            .map(|id| network.wiring.name(id))
+            .filter_map(|name| Some((name.to_string(), network.modules.get(name)?.clone())))
+            .collect();
+        Subnetwork { output: output.to_string(), modules, sent_high: false }
+    }
+
+    /// The subnetwork after one more press, if the press ends.
+    fn press(&self, wiring: &Graph) -> Option<Self> {
+        let mut next = self.clone();
+        let mut presses = HashMap::new();
+        get_counts(&mut next.modules, wiring, std::slice::from_ref(&self.output), 0, &mut presses)?;
+        next.sent_high = presses.contains_key(&self.output);
+        Some(next)
+    }
+
+    fn memory(&self) -> Vec<bool> {
+        let mut names = self.modules.keys().collect::<Vec<_>>();
+        names.sort();
+        names.into_iter().flat_map(|name| self.modules[name].memory()).collect()
+    }
+}
+
+impl PartialEq for Subnetwork {
+    fn eq(&self, other: &Self) -> bool {
+        self.output == other.output && self.sent_high == other.sent_high && self.memory() == other.memory()
+    }
+}
+
+impl Eq for Subnetwork {}
+
+impl Hash for Subnetwork {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.output.hash(state);
+        self.sent_high.hash(state);
+        self.memory().hash(state);
+    }
+}
+
+#[derive(Clone)]
+pub struct Network {
+    /// The modules by name, in their state before any button push.
+    pub modules: HashMap<String, Box<dyn Module>>,
+    /// An edge from every module to each module it sends its pulses to, in order.
+    pub wiring: Graph,
+}
+
+/// Parses one `%name -> a, b` line per module, `%` for a flip-flop and `&` for a conjunction.
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let module_regex = Regex::new(r"^(?<name>(%|&)?\w+) -> (?<destinations>\w+(, \w+)*)$").unwrap();
+    let mut modules = HashMap::<String, Box<dyn Module>>::new();
+    let mut wiring = Graph::directed();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let result = parse::captures(&module_regex, line, Position::start_of(number))?;
+        let mut name = result["name"].to_owned();
+
+        let module: Box<dyn Module> = if name.starts_with('&') {
+            name = name[1..].to_string();
+            Box::new(ConjunctionModule::new())
+        } else if name.starts_with('%') {
+            name = name[1..].to_string();
+            Box::new(FlipFlopModule::new())
+        } else if name == "broadcaster" {
+            Box::new(SourceModule::new())
+        } else {
+            debug!("inserting default module {name};");
+            Box::new(DefaultModule::new())
+        };
+        modules.insert(name.clone(), module);
+        
+        for destination in result["destinations"].split(", ") {
+            wiring.connect(&name, destination, ());
+        }
+    }
+
+    for &(from, to, ()) in wiring.edges() {
+        if let Some(module) = modules.get_mut(wiring.name(to)) {
+            module.register_input(wiring.name(from));
+        }
+    }
+    trace!("{}", wiring.to_dot());
+    Ok(Network { modules, wiring })
+}
+
+/// The number of low pulses times the number of high pulses sent during `presses` pushes of the button, unless a
+/// push never ends.
+pub fn pulse_product(network: &Network, presses: usize) -> Result<usize, SolveError> {
+    let Network { mut modules, wiring } = network.clone();
+    let mut counts = vec![0, 0];
+    let mut deps: HashMap<String, Vec<usize>> = HashMap::new();
+    for i in 0..presses {
+        let (a, b) = get_counts(&mut modules, &wiring, &[], i, &mut deps).ok_or_else(endless_press)?;
+        counts[0] += a;
+        counts[1] += b;
+    }
+    debug!("counts: {:?}", counts);
+    counts[0].checked_mul(counts[1]).ok_or_else(|| SolveError::new("the product of the pulse counts overflows"))
+}
+
+/// Presses the button until `to` gets a low pulse, and returns the number of presses.
+pub fn first_low_pulse(network: &Network, to: &str) -> Result<usize, SolveError> {
+    let Network { mut modules, wiring } = network.clone();
+    for press in 1..=MAX_PRESSES {
+        let mut pulses = VecDeque::from([("button".to_string(), "broadcaster".to_string(), Pulse::Low)]);
+        let mut sent = 0;
+        while let Some(pulse) = pulses.pop_front() {
+            if pulse.1 == to && pulse.2 == Pulse::Low {
+                return Ok(press);
+            }
+            sent += 1;
+            if sent == MAX_PULSES {
+                return Err(endless_press());
+            }
+            pulses.extend(deliver(&mut modules, &wiring, &pulse));
+        }
+    }
+    Err(SolveError::new(format!("{to} gets no low pulse within {MAX_PRESSES} presses")))
+}
+
+fn endless_press() -> SolveError {
+    SolveError::new(format!("a button press sends more than {MAX_PULSES} pulses, the modules keep sending each other pulses"))
+}
+
+/// Puzzle presses send a few hundred pulses.
+const MAX_PULSES: usize = 100_000;
+
+/// The parts of puzzle networks repeat after a few thousand presses.
+const MAX_PRESSES: usize = 100_000;
+
+/// Part 2 tries every way of picking a press of each input's cycle, of which puzzle inputs have one.
+const MAX_COMBINATIONS: usize = 100_000;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Model = Network;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Network, ParseError> {
+        parse(input)
+    }
+
+    fn part1(network: &Network) -> Result<usize, SolveError> {
+        pulse_product(network, 1000)
+    }
+
+    /// In puzzle inputs `rx` is fed by a single conjunction, which sends it a low pulse once all of its inputs sent
+    /// it a high pulse during the same button press. Each input is driven by its own part of the network, whose states
+    /// repeat long before the whole network does, so the answer is either a press before the last of them starts
+    /// repeating, or the first press on which all of their cycles line up. Other networks have their button pressed
+    /// until `rx` gets a low pulse.
+    fn part2(network: &Network) -> Result<usize, SolveError> {
+        let inputs = network.wiring.reversed();
+        let rx = inputs.id("rx").ok_or_else(|| SolveError::new("there is no module rx"))?;
+        let &[(rx_parent, ())] = inputs.neighbours(rx) else {
+            return first_low_pulse(network, "rx");
+        };
+        if network.modules.get(inputs.name(rx_parent)).is_none_or(|module| module.prefix() != "&") {
+            return first_low_pulse(network, "rx");
+        }
+        let rx_parent_dependencies = inputs.neighbours(rx_parent).iter().map(|&(dep, ())| inputs.name(dep).to_string()).collect::<Vec<String>>();
+        let rx_parent = inputs.name(rx_parent);
+        debug!("rx parent: {rx_parent}: {rx_parent_dependencies:?}");
+        if rx_parent_dependencies.is_empty() {
+            return Err(SolveError::new(format!("{rx_parent}, which feeds rx, has no inputs")));
+        }
+        let highs = rx_parent_dependencies.iter()
+            .map(|dep| {
+                // a press that never ends is a state of its own, which repeats right away
+                let start = Some(Subnetwork::new(network, dep));
+                let history = cycle::find_cycle_within(start, MAX_PRESSES, |sub| sub.as_ref()?.press(&network.wiring))
+                    .ok_or_else(|| SolveError::new(format!("the modules feeding {dep} do not repeat within {MAX_PRESSES} presses")))?;
+                if history.states.iter().any(Option::is_none) {
+                    return Err(endless_press());
+                }
+                debug!("{dep}: cycle start: {} cycle length: {}", history.cycle.tail, history.cycle.period);
+                Ok(Occurrences::new(history.cycle, history.states.iter().map(|sub| sub.as_ref().is_some_and(|sub| sub.sent_high))))
+            })
+            .collect::<Result<Vec<Occurrences>, SolveError>>()?;
+        debug!("high pulses: {highs:?}");
+        if let Some(presses) = cycle::first_common_in_tails(&highs) {
+            return Ok(presses);
+        }
+        let cycles = highs.into_iter().map(|high| high.repeating).collect::<Vec<Vec<(i128, i128)>>>();
+        if math::combinations_of_any(&cycles).is_none_or(|combinations| combinations > MAX_COMBINATIONS) {
+            return Err(SolveError::new(format!("the inputs of {rx_parent} send high pulses too often to try every combination")));
+        }
+        let presses = math::first_common_time_of_any(&cycles)
+            .ok_or_else(|| SolveError::new(format!("the inputs of {rx_parent} never send high pulses on the same press")))?;
+        usize::try_from(presses).map_err(|_| SolveError::new(format!("rx only gets a low pulse after {presses} presses")))
+    }
+}
+
+/// The network between pulses, with the pulses still on their way.
+#[derive(Clone)]
+pub struct Pulses {
+    pub modules: HashMap<String, Box<dyn Module>>,
+    pub wiring: Rc<Graph>,
+    /// The pulses sent and not received yet, in the order they are received.
+    pub queue: VecDeque<(String, String, Pulse)>,
+    pub presses: usize,
+    /// How many low and high pulses were received.
+    pub counts: [usize; 2],
+}
+
+/// The pulses as the puzzle writes them, `a -high-> b`.
+fn describe((from, to, pulse): &(String, String, Pulse)) -> String {
+    format!("{from} -{}-> {to}", if *pulse == Pulse::High { "high" } else { "low" })
+}
+
+/// Delivers one pulse per step, pushing the button whenever no pulse is on its way. There is no last step.
+impl Simulation for Day20 {
+    type State = Pulses;
+
+    fn start(input: &str) -> Result<Pulses, ParseError> {
+        let Network { modules, wiring } = parse(input)?;
+        Ok(Pulses { modules, wiring: Rc::new(wiring), queue: VecDeque::new(), presses: 0, counts: [0, 0] })
+    }
+
+    fn step(state: &Pulses) -> Option<(Pulses, Vec<String>)> {
+        let mut next = state.clone();
+        let mut events = Vec::<String>::new();
+        if next.queue.is_empty() {
+            next.presses += 1;
+            events.push(format!("button press {}", next.presses));
+            next.queue.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+        }
+        let sent = next.queue.pop_front().unwrap();
+        next.counts[sent.2.index()] += 1;
+        let replies = deliver(&mut next.modules, &next.wiring, &sent);
+        next.queue.extend(replies);
+        events.push(describe(&sent));
+        Some((next, events))
+    }
+
+    /// A module per line, with what it remembers: whether a flip-flop is on, and the last pulse of each input of a
+    /// conjunction, high as a filled circle. The modules that remember a high pulse are yellow, and the one about to
+    /// receive a pulse is red.
+    fn picture(state: &Pulses) -> Grid<Cell> {
+        let mut names = state.modules.keys().collect::<Vec<_>>();
+        names.sort();
+        let width = names.iter().map(|name| name.len() + 1).max().unwrap_or(0);
+        let lines = names.iter().map(|name| {
+            let module = &state.modules[*name];
+            let memory = module.memory().iter().map(|&high| if high { '●' } else { '○' }).collect::<String>();
+            let destinations = state.wiring.neighbours(state.wiring.id(name).unwrap()).iter().map(|&(to, ())| state.wiring.name(to)).collect::<Vec<_>>();
+            format!("{:>width$} {memory:<12} -> {}", format!("{}{name}", module.prefix()), destinations.join(", "))
+        }).collect::<Vec<_>>();
+        let mut picture = render::text(&lines, Color::GREY);
+        let receiving = state.queue.front().map(|(_, to, _)| to);
+        for (y, name) in names.iter().enumerate() {
+            let color = if receiving == Some(*name) {
+                Color::RED
+            } else if state.modules[*name].memory().contains(&true) {
+                Color::YELLOW
+            } else {
+                continue;
+            };
+            for x in 0..picture.width() {
+                picture[(x, y)].foreground = color;
+            }
+        }
+        picture
+    }
+
+    fn details(state: &Pulses) -> Vec<String> {
+        let mut details = vec![
+            format!("button presses: {}", state.presses),
+            format!("pulses received: {} low, {} high", state.counts[0], state.counts[1]),
+            format!("pulses on their way: {}", state.queue.len()),
+        ];
+        details.extend(state.queue.iter().take(10).map(|sent| format!("  {}", describe(sent))));
+        details
+    }
+}
+
+/// A network shaped like the puzzle's: the broadcaster starts two to four counters of `size` flip-flops each, at
+/// least 2 and at most 12. A counter's conjunction watches the flip-flops of the set bits of its period and resets
+/// the others when they are all on, which its inverter passes on to the conjunction in front of `rx`.
+impl Generator for Day20 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let bits = size.clamp(2, 12);
+        let mut names = std::collections::HashSet::from(["rx".to_string()]);
+        let mut name = |rng: &mut Rng| loop {
+            let name = rng.word(2);
+            if names.insert(name.clone()) {
+                break name;
+            }
+        };
+        let hub = name(rng);
+        let mut starts = Vec::<String>::new();
+        let mut lines = vec![format!("&{hub} -> rx")];
+        for _ in 0..rng.between(2, 4) {
+            // odd and using every bit, so the counter fires every `period` presses right from the start
+            let period = (1 << (bits - 1)) | rng.below(1 << (bits - 1)) | 1;
+            let flip_flops = (0..bits).map(|_| name(rng)).collect::<Vec<_>>();
+            let (conjunction, inverter) = (name(rng), name(rng));
+            for (bit, flip_flop) in flip_flops.iter().enumerate() {
+                let mut destinations = flip_flops.get(bit + 1).into_iter().cloned().collect::<Vec<_>>();
+                if period & (1 << bit) != 0 {
+                    destinations.push(conjunction.clone());
+                }
+                lines.push(format!("%{flip_flop} -> {}", destinations.join(", ")));
+            }
+            let mut resets = (0..bits).filter(|&bit| bit == 0 || period & (1 << bit) == 0).map(|bit| flip_flops[bit].clone()).collect::<Vec<_>>();
+            resets.push(inverter.clone());
+            lines.push(format!("&{conjunction} -> {}", resets.join(", ")));
+            lines.push(format!("&{inverter} -> {hub}"));
+            starts.push(flip_flops[0].clone());
+        }
+        rng.shuffle(&mut lines);
+        format!("broadcaster -> {}\n{}\n", starts.join(", "), lines.join("\n"))
+    }
+}
@@ -0,0 +1,221 @@
+//! Day 21: Step Counter. Counts the garden plots an elf can be on after an exact number of steps, on the map itself
+//! and on the map repeating forever in every direction.
+
+use aoc_common::{Cell, Check, Color, Direction, Generator, Grid, Oracle, ParseError, Position, Rng, Simulation, Solution, SolveError};
+use log::debug;
+use std::collections::HashSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Starting, Garden, Rock,
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Garden),
+            '#' => Some(Tile::Rock),
+            'S' => Some(Tile::Starting),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Tile::Starting => "S",  
+                Tile::Garden => ".",
+                Tile::Rock => "#",
+            }
+        )
+    }
+}
+
+pub struct Garden {
+    pub tiles: Grid<Tile>,
+    /// Where the elf starts, which is a plot too.
+    pub start: (isize, isize),
+}
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    let tiles = Grid::from_str(input, Tile::from_char)?;
+    let Some((x, y)) = tiles.position(|tile| *tile == Tile::Starting) else {
+        return Err(ParseError::new(Position::start_of(1), input.lines().next().unwrap_or(""), "expected a starting position S"));
+    };
+    Ok(Garden { tiles, start: (x as isize, y as isize) })
+}
+
+/// Counts the plots reachable in exactly `steps` steps for each entry of `steps`, on the infinitely repeating map.
+pub fn reachable_plots(garden: &Garden, steps: &[usize]) -> Vec<usize> {
+    let max_steps = *steps.iter().max().unwrap();
+    let mut distances = HashMap::<(isize, isize), usize>::new();
+    let mut queue = VecDeque::<(isize, isize)>::new();
+    distances.insert(garden.start, 0);
+    queue.push_back(garden.start);
+    while let Some(pos) = queue.pop_front() {
+        let distance = distances[&pos];
+        if distance == max_steps {
+            continue;
+        }
+        for direction in Direction::ALL {
+            let next_position = direction.offset(pos);
+            if garden.tiles.wrapping(next_position).is_some_and(|tile| *tile != Tile::Rock) && !distances.contains_key(&next_position) {
+                distances.insert(next_position, distance + 1);
+                queue.push_back(next_position);
+            }
+        }
+    }
+    steps.iter().map(|steps| distances.values().filter(|distance| **distance <= *steps && **distance % 2 == *steps % 2).count()).collect()
+}
+
+/// The start row and column are free of rocks, so the reachable area grows by one copy of the map in every direction
+/// each `width` steps and the count is a quadratic function of the number of copies crossed. That takes a square map
+/// with the start in the middle.
+pub fn extrapolated_plots(garden: &Garden, total_steps: usize) -> Result<usize, SolveError> {
+    let size = garden.tiles.width();
+    let (x, y) = garden.start;
+    if garden.tiles.height() != size || x != y || x as usize != size / 2 {
+        return Err(SolveError::new("the map is not a square with the start in the middle"));
+    }
+    let rocks = (0..size as isize).any(|i| garden.tiles.get((i, y)) == Some(&Tile::Rock) || garden.tiles.get((x, i)) == Some(&Tile::Rock));
+    if rocks {
+        return Err(SolveError::new("there are rocks in the row or column of the start"));
+    }
+    let offset = total_steps % size;
+    let samples = reachable_plots(garden, &[offset, offset + size, offset + 2 * size]).into_iter().map(|sample| sample as i128).collect::<Vec<i128>>();
+    debug!("samples: {samples:?}");
+    let n = (total_steps / size) as i128;
+    let first_difference = samples[1] - samples[0];
+    let second_difference = samples[2] + samples[0] - 2 * samples[1];
+    let plots = samples[0] + first_difference * n + second_difference * n * (n - 1).max(0) / 2;
+    usize::try_from(plots).map_err(|_| SolveError::new(format!("the plots do not grow as the square of the steps, the fit gives {plots}")))
+}
+
+/// Counts the plots reachable in exactly `steps` steps on the infinitely repeating map by taking every step.
+pub fn simulated_plots(garden: &Garden, steps: usize) -> usize {
+    let mut positions = HashSet::from([garden.start]);
+    for _ in 0..steps {
+        positions = positions.iter()
+            .flat_map(|&position| Direction::ALL.map(|direction| direction.offset(position)))
+            .filter(|&position| garden.tiles.wrapping(position).is_some_and(|tile| *tile != Tile::Rock))
+            .collect();
+    }
+    positions.len()
+}
+
+/// The plots one step away from any of `plots`, without leaving the map.
+pub fn next_plots(garden: &Garden, plots: &HashSet<(isize, isize)>) -> HashSet<(isize, isize)> {
+    plots.iter()
+        .flat_map(|&position| Direction::ALL.map(|direction| direction.offset(position)))
+        .filter(|&position| garden.tiles.get(position).is_some_and(|tile| *tile != Tile::Rock))
+        .collect()
+}
+
+/// Counts the plots reachable in exactly `steps` steps without leaving the map.
+pub fn bounded_plots(garden: &Garden, steps: usize) -> usize {
+    (0..steps).fold(HashSet::from([garden.start]), |plots, _| next_plots(garden, &plots)).len()
+}
+
+/// Part 2's number of steps.
+pub const TOTAL_STEPS: usize = 26501365;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Model = Garden;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Garden, ParseError> {
+        parse(input)
+    }
+
+    fn part1(garden: &Garden) -> Result<usize, SolveError> {
+        Ok(bounded_plots(garden, 64))
+    }
+
+    fn part2(garden: &Garden) -> Result<usize, SolveError> {
+        extrapolated_plots(garden, TOTAL_STEPS)
+    }
+}
+
+/// Part 2's number of steps is out of reach of the simulation, it checks the extrapolation for a few copies of the
+/// map beyond the three it is fitted to instead.
+impl Oracle for Day21 {
+    fn checks(garden: &Garden) -> Vec<Check> {
+        let size = garden.tiles.width();
+        (3..=4)
+            .map(|copies| TOTAL_STEPS % size + copies * size)
+            .map(|steps| Check::new(format!("{steps} steps"), extrapolated_plots(garden, steps), simulated_plots(garden, steps)))
+            .collect()
+    }
+}
+
+/// The plots the elf can be on after some number of steps.
+#[derive(Clone)]
+pub struct Walk {
+    pub garden: Rc<Garden>,
+    pub plots: HashSet<(isize, isize)>,
+    pub steps: usize,
+}
+
+/// Takes the steps of part 1 one at a time, and goes on stepping back and forth once the whole map is reached.
+impl Simulation for Day21 {
+    type State = Walk;
+
+    fn start(input: &str) -> Result<Walk, ParseError> {
+        let garden = parse(input)?;
+        let plots = HashSet::from([garden.start]);
+        Ok(Walk { garden: Rc::new(garden), plots, steps: 0 })
+    }
+
+    fn step(state: &Walk) -> Option<(Walk, Vec<String>)> {
+        let next = Walk { garden: state.garden.clone(), plots: next_plots(&state.garden, &state.plots), steps: state.steps + 1 };
+        let events = vec![format!("step {}: {} plots", next.steps, next.plots.len())];
+        Some((next, events))
+    }
+
+    fn picture(state: &Walk) -> Grid<Cell> {
+        let mut picture = state.garden.tiles.map(|tile| match tile {
+            Tile::Rock => Cell::new('#', Color::GREY),
+            _ => Cell::new(' ', Color::GREY),
+        });
+        for &(x, y) in &state.plots {
+            picture[(x as usize, y as usize)] = Cell::new('O', Color::BLACK).on(Color::GREEN);
+        }
+        let (x, y) = state.garden.start;
+        picture[(x as usize, y as usize)].foreground = Color::RED;
+        picture
+    }
+
+    fn details(state: &Walk) -> Vec<String> {
+        vec![format!("steps: {}", state.steps), format!("plots: {}", state.plots.len())]
+    }
+}
+
+/// A square garden `size` wide, made odd and at least 5, with the start in the middle. Like in the puzzle, the
+/// middle row and column, the edges and a diamond halfway out are free of rocks, which part 2 relies on.
+impl Generator for Day21 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(5) | 1;
+        let middle = size / 2;
+        let mut tiles = Grid::filled(size, size, '.');
+        for (x, y) in tiles.positions().collect::<Vec<_>>() {
+            let distance = x.abs_diff(middle) + y.abs_diff(middle);
+            let clear = x == middle || y == middle || x == 0 || y == 0 || x == size - 1 || y == size - 1 || distance.abs_diff(middle) <= 1;
+            if !clear && rng.chance(0.15) {
+                tiles[(x, y)] = '#';
+            }
+        }
+        tiles[(middle, middle)] = 'S';
+        tiles.to_string()
+    }
+}
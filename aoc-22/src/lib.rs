@@ -0,0 +1,313 @@
+//! Day 22: Sand Slabs. Lets a snapshot of falling bricks settle, and works out which bricks could be taken away
+//! without any other brick falling and how many would fall otherwise.
+use aoc_common::{parse, Cell, Color, Generator, Grid, ParseError, Rng, Simulation, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+
+trait Vector {
+    fn add(&self, other: &Self) -> Self;
+}
+
+/// A cube as its x, y and z, z going up from 1 just above the ground.
+pub type Position = [isize; 3];
+
+impl Vector for Position {
+    fn add(&self, other: &Self) -> Self {
+        let mut result = [0; 3];
+        for i in 0..3 {
+            result[i] = self[i] + other[i];
+        }
+        result
+    }
+}
+
+/// The largest coordinate of a brick.
+pub const MAX_COORDINATE: isize = 1 << 32;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// A brick, as the cubes at both of its ends, the one with the smaller coordinates first.
+pub struct Block {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Block {
+    fn new(line: &str, start: aoc_common::Position) -> Result<Self, ParseError> {
+        let block_regex = Regex::new(r"^\d+,\d+,\d+~\d+,\d+,\d+$").unwrap();
+        parse::captures(&block_regex, line, start)?;
+        let values = aoc_common::numbers::<isize>(line, start)?;
+        if let Some(value) = values.iter().find(|&&value| value > MAX_COORDINATE) {
+            return Err(ParseError::new(start, &value.to_string(), "expected a coordinate of at most 2^32"));
+        }
+        if values[2].min(values[5]) < 1 {
+            return Err(ParseError::new(start, line, "expected a brick above the ground, at a z of at least 1"));
+        }
+        // the ends can be given either way around
+        let (min, max) = ([values[0], values[1], values[2]], [values[3], values[4], values[5]]);
+        Ok(Block { min: [0, 1, 2].map(|i| min[i].min(max[i])), max: [0, 1, 2].map(|i| min[i].max(max[i])) })
+    }
+
+    /// Whether the bricks share a cube.
+    pub fn intersects(&self, other: &Block) -> bool {
+        for i in 0..3 {
+            if self.min[i] > other.max[i] || self.max[i] < other.min[i] {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn offset(&mut self, x: isize, y: isize, z: isize) {
+        self.min = self.min.add(&[ x, y, z ]);
+        self.max = self.max.add(&[ x, y, z ]);
+    }
+}
+
+/// Lets the bricks fall, the lowest first, each until it rests on the ground or on a brick that already fell.
+pub fn fall(blocks: &mut [Block]) {
+    let order = fall_order(blocks);
+    for fallen in 0..order.len() {
+        land(blocks, &order, fallen);
+    }
+}
+
+/// The bricks in the order they land, the lowest first.
+pub fn fall_order(blocks: &[Block]) -> Vec<usize> {
+    let mut order = (0..blocks.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&i| blocks[i].min[2]);
+    order
+}
+
+/// Lets the brick `order[fallen]` fall until it rests on the ground or on a brick before it in `order`, which have all
+/// landed. Returns how far it fell.
+pub fn land(blocks: &mut [Block], order: &[usize], fallen: usize) -> isize {
+    let i = order[fallen];
+    let floor = order[..fallen].iter()
+        .map(|&j| blocks[j])
+        .filter(|below| (0..2).all(|axis| below.min[axis] <= blocks[i].max[axis] && blocks[i].min[axis] <= below.max[axis]))
+        .map(|below| below.max[2])
+        .max()
+        .unwrap_or(0);
+    let drop = blocks[i].min[2] - floor - 1;
+    if drop > 0 {
+        blocks[i].offset(0, 0, -drop);
+    }
+    drop.max(0)
+}
+
+/// Whether every other brick stays put when the brick at `block_index` is taken away. The bricks are moved while
+/// checking, but end up where they were.
+pub fn is_stable(blocks: &mut [Block], block_index: usize) -> bool {
+    for i in 0..blocks.len() {
+        if i == block_index {
+            continue;
+        }
+        if blocks[i].min[2] == 1 {
+            continue;
+        }
+        blocks[i].offset(0, 0, -1);
+        let mut had_overlap = false;
+        for j in 0..blocks.len() {
+            if j == i || j == block_index {
+                continue;
+            }
+            if blocks[i].intersects(&blocks[j]) {
+                had_overlap = true;
+            }
+        }
+        blocks[i].offset(0, 0, 1);
+        if !had_overlap {
+            return false;
+        }
+    }
+    true
+}
+
+/// The number of other bricks that fall when the brick at `block_index` is taken away.
+pub fn get_falling_blocks(blocks: &[Block], block_index: usize) -> usize {
+    let mut new_blocks = blocks.iter().enumerate().filter(|(i, _)| *i != block_index).map(|(_, block)| *block).collect::<Vec<Block>>();
+    fall(&mut new_blocks);
+    let mut displacements = 0;
+    for i in 0..blocks.len() {
+        if i == block_index {
+            continue;
+        }
+        let old_block = blocks[i];
+        let new_block = if i < block_index {
+            new_blocks[i]
+        } else {
+            new_blocks[i-1]
+        };
+        if old_block != new_block {
+            displacements += 1;
+        }
+    }
+    displacements
+}
+
+/// Parses one `x,y,z~x,y,z` brick per line, where the snapshot shows it falling.
+pub fn snapshot(input: &str) -> Result<Vec<Block>, ParseError> {
+    aoc_common::numbered_lines(input).iter()
+        .map(|(number, line)| Block::new(line, aoc_common::Position::start_of(*number)))
+        .collect()
+}
+
+/// Parses the snapshot and lets the bricks settle.
+pub fn parse(input: &str) -> Result<Vec<Block>, ParseError> {
+    let mut blocks = snapshot(input)?;
+    fall(&mut blocks);
+    trace!("blocks: {blocks:?}");
+    Ok(blocks)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Model = Vec<Block>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Block>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(blocks: &Vec<Block>) -> Result<usize, SolveError> {
+        let mut blocks = blocks.clone();
+        let mut stable_count = 0;
+        for i in 0..blocks.len() {
+            if is_stable(&mut blocks, i) {
+                trace!("Block {} is stable", i);
+                stable_count += 1;
+            }
+        }
+        Ok(stable_count)
+    }
+
+    fn part2(blocks: &Vec<Block>) -> Result<usize, SolveError> {
+        let mut blocks = blocks.clone();
+        let mut falling_count = 0;
+        for i in 0..blocks.len() {
+            if !is_stable(&mut blocks, i) {
+                let displacements = get_falling_blocks(&blocks, i);
+                trace!("removing block {} will cause {} blocks to fall", i, displacements);
+                falling_count += displacements;
+            }
+        }
+        Ok(falling_count)
+    }
+}
+
+/// The bricks while they settle, those before `landed` in `order` have come to rest.
+#[derive(Clone)]
+pub struct Settling {
+    pub blocks: Vec<Block>,
+    pub order: Rc<Vec<usize>>,
+    pub landed: usize,
+}
+
+/// The widest and tallest part of the snapshot that is drawn, from its left edge and from the ground up. Coordinates
+/// go up to 2^32.
+const MAX_VIEW_WIDTH: isize = 120;
+const MAX_VIEW_HEIGHT: isize = 400;
+
+/// Bricks are named after their line in the input, and drawn as a letter that goes through the alphabet with it.
+fn label(index: usize) -> char {
+    (b'A' + (index % 26) as u8) as char
+}
+
+/// Lands one brick per step, the lowest first, until all of them rest.
+impl Simulation for Day22 {
+    type State = Settling;
+
+    fn start(input: &str) -> Result<Settling, ParseError> {
+        let blocks = snapshot(input)?;
+        let order = fall_order(&blocks);
+        Ok(Settling { blocks, order: Rc::new(order), landed: 0 })
+    }
+
+    fn step(state: &Settling) -> Option<(Settling, Vec<String>)> {
+        if state.landed == state.blocks.len() {
+            return None;
+        }
+        let mut next = state.clone();
+        let drop = land(&mut next.blocks, &next.order, next.landed);
+        let i = next.order[next.landed];
+        next.landed += 1;
+        let block = next.blocks[i];
+        let supports = next.order[..next.landed - 1].iter()
+            .filter(|&&j| next.blocks[j].max[2] + 1 == block.min[2] && (0..2).all(|axis| next.blocks[j].min[axis] <= block.max[axis] && block.min[axis] <= next.blocks[j].max[axis]))
+            .map(|&j| format!("brick {}", j + 1))
+            .collect::<Vec<_>>();
+        let resting_on = if supports.is_empty() { "the ground".to_string() } else { supports.join(", ") };
+        let events = vec![format!("brick {} ({}) falls {drop} to z={} and rests on {resting_on}", i + 1, label(i), block.min[2])];
+        Some((next, events))
+    }
+
+    /// The bricks seen from the front, x across and z up, the nearest one in front. The bricks that landed are green,
+    /// the last of them yellow, and the ones still falling grey.
+    fn picture(state: &Settling) -> Grid<Cell> {
+        let left = state.blocks.iter().map(|block| block.min[0]).min().unwrap_or(0);
+        let right = state.blocks.iter().map(|block| block.max[0]).max().unwrap_or(0).min(left + MAX_VIEW_WIDTH - 1);
+        let top = state.blocks.iter().map(|block| block.max[2]).max().unwrap_or(0).min(MAX_VIEW_HEIGHT);
+        let (width, height) = ((right - left + 1) as usize, top as usize + 1);
+        let mut picture = Grid::filled(width, height, Cell::new(' ', Color::GREY));
+        let mut nearest = Grid::filled(width, height, isize::MAX);
+        for x in 0..width {
+            picture[(x, height - 1)] = Cell::new('-', Color::GREY);
+        }
+        let landed = state.order[..state.landed].iter().copied().collect::<HashSet<usize>>();
+        for (i, block) in state.blocks.iter().enumerate() {
+            let color = if state.landed > 0 && state.order[state.landed - 1] == i {
+                Color::YELLOW
+            } else if landed.contains(&i) {
+                Color::GREEN
+            } else {
+                Color::GREY
+            };
+            for x in block.min[0]..=block.max[0].min(right) {
+                for z in block.min[2]..=block.max[2].min(top) {
+                    let position = ((x - left) as usize, (top - z) as usize);
+                    if block.min[1] < nearest[position] {
+                        nearest[position] = block.min[1];
+                        picture[position] = Cell::new(label(i), Color::BLACK).on(color);
+                    }
+                }
+            }
+        }
+        picture
+    }
+
+    fn details(state: &Settling) -> Vec<String> {
+        let height = state.order[..state.landed].iter().map(|&i| state.blocks[i].max[2]).max().unwrap_or(0);
+        vec![
+            format!("bricks landed: {} of {}", state.landed, state.blocks.len()),
+            format!("height of the landed bricks: {height}"),
+        ]
+    }
+}
+
+/// `size` bricks of up to four cubes over a 10 by 10 area, dropped in a random order from a little above where they
+/// would land, so none of them overlap.
+impl Generator for Day22 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut heights = [[0; 10]; 10];
+        let mut bricks = Vec::<String>::new();
+        for _ in 0..size {
+            let axis = rng.below(3);
+            let mut extent = [0; 3];
+            extent[axis] = rng.between(0, 3) as usize;
+            let (x, y) = (rng.below(10 - extent[0]), rng.below(10 - extent[1]));
+            let footprint = (x..=x + extent[0]).flat_map(|x| (y..=y + extent[1]).map(move |y| (x, y)));
+            let z = footprint.clone().map(|(x, y)| heights[x][y]).max().unwrap() + 1 + rng.below(4);
+            for (x, y) in footprint {
+                heights[x][y] = z + extent[2];
+            }
+            bricks.push(format!("{x},{y},{z}~{},{},{}", x + extent[0], y + extent[1], z + extent[2]));
+        }
+        rng.shuffle(&mut bricks);
+        bricks.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,267 @@
+//! Day 23: A Long Walk. Finds the longest hike through the forest that never steps on a tile twice, with the slopes
+//! only going downhill and then as ordinary paths.
+
+use aoc_common::{Cell, Color, Direction, Generator, Graph, Grid, ParseError, Position, Render, Rng, Solution, SolveError};
+use log::trace;
+
+/// A tile of the map. A slope can only be left in its direction, unless slopes are disregarded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Path, Forest, Slope(Direction)
+}
+
+impl Tile {
+    fn from_char(character: char) -> Option<Tile> {
+        match character {
+            '#' => Some(Tile::Forest),
+            '.' => Some(Tile::Path),
+            '>' | 'v' | '<' | '^' => Direction::from_char(character).map(Tile::Slope),
+            _ => None,
+        }
+    }
+}
+
+/// The length of the longest hike over the corridors of `junctions`, from the first junction to the last, or `None`
+/// if the last cannot be reached. There can be at most 64 junctions, the search keeps the visited ones as the bits of
+/// a number.
+pub fn longest_path(junctions: &Graph<usize>) -> Option<usize> {
+    assert!(junctions.len() <= 64, "{} junctions are more than 64", junctions.len());
+    let neighbours = (0..junctions.len()).map(|junction| junctions.neighbours(junction).to_vec()).collect::<Vec<_>>();
+    longest_path_from(0, 0, &neighbours)
+}
+
+/// The length of the longest hike from `position` to the last junction that avoids the `visited` junctions.
+fn longest_path_from(position: usize, visited: u64, neighbours: &[Vec<(usize, usize)>]) -> Option<usize> {
+    if visited & 1 << position != 0 {
+        return None;
+    }
+    if position == neighbours.len() - 1 {
+        return Some(0);
+    }
+    let visited = visited | 1 << position;
+    neighbours[position].iter().filter_map(|&(next, distance)| Some(distance + longest_path_from(next, visited, neighbours)?)).max()
+}
+
+/// The junctions of the longest hike over the corridors of `junctions`, from the first junction to the last, or `None`
+/// if the last cannot be reached. Slower than `longest_path`, it keeps every hike it tries.
+pub fn longest_hike(junctions: &Graph<usize>) -> Option<Vec<usize>> {
+    assert!(junctions.len() <= 64, "{} junctions are more than 64", junctions.len());
+    let neighbours = (0..junctions.len()).map(|junction| junctions.neighbours(junction).to_vec()).collect::<Vec<_>>();
+    let (_, mut hike) = longest_hike_from(0, 0, &neighbours)?;
+    hike.reverse();
+    Some(hike)
+}
+
+/// The length and the junctions of the longest hike from `position`, last junction first.
+fn longest_hike_from(position: usize, visited: u64, neighbours: &[Vec<(usize, usize)>]) -> Option<(usize, Vec<usize>)> {
+    if visited & 1 << position != 0 {
+        return None;
+    }
+    if position == neighbours.len() - 1 {
+        return Some((0, vec![position]));
+    }
+    let visited = visited | 1 << position;
+    let (length, mut hike) = neighbours[position].iter()
+        .filter_map(|&(next, distance)| longest_hike_from(next, visited, neighbours).map(|(length, hike)| (distance + length, hike)))
+        .max_by_key(|(length, _)| *length)?;
+    hike.push(position);
+    Some((length, hike))
+}
+
+/// The name of the junction at `position` in the graph of junctions.
+fn junction_name((x, y): (isize, isize)) -> String {
+    format!("{x},{y}")
+}
+
+pub fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+    let tiles = Grid::from_str(input, Tile::from_char)?;
+    if tiles.height() == 0 || !tiles.row(0).contains(&Tile::Path) {
+        return Err(ParseError::new(Position::start_of(1), input.lines().next().unwrap_or(""), "expected a path in the first row"));
+    }
+    Ok(tiles)
+}
+
+/// The junctions of the map, every tile with other than two neighbouring paths, in reading order, and the lengths of
+/// the corridors between them. Slopes count as paths.
+pub fn junction_graph(tiles: &Grid<Tile>) -> Graph<usize> {
+    corridors(tiles, false)
+}
+
+/// Like `junction_graph`, but with an edge for each way a corridor can be walked without climbing a slope.
+pub fn downhill_junction_graph(tiles: &Grid<Tile>) -> Graph<usize> {
+    corridors(tiles, true)
+}
+
+/// The junctions in reading order, the nodes of both graphs.
+fn junction_positions(tiles: &Grid<Tile>) -> Vec<(isize, isize)> {
+    tiles.iter()
+        .filter(|(position, tile)| **tile != Tile::Forest && tiles.neighbours4(*position).filter(|neighbour| tiles[*neighbour] != Tile::Forest).count() != 2)
+        .map(|((x, y), _)| (x as isize, y as isize))
+        .collect()
+}
+
+/// A walk from a junction along a corridor: the tiles after the junction up to the junction it ends at, and whether
+/// every slope on the way leads the way it is walked.
+struct Corridor {
+    tiles: Vec<(isize, isize)>,
+    downhill: bool,
+}
+
+/// Walks the corridor leaving the junction at `start` in `direction`, if there is one.
+fn corridor(tiles: &Grid<Tile>, start: (isize, isize), direction: Direction, is_junction: impl Fn((isize, isize)) -> bool) -> Option<Corridor> {
+    let enterable = |position: (isize, isize), direction: Direction| match tiles.get(position) {
+        Some(Tile::Slope(slope)) => *slope == direction,
+        Some(tile) => *tile != Tile::Forest,
+        None => false,
+    };
+    let mut previous = start;
+    let mut current = direction.offset(start);
+    if tiles.get(current).is_none_or(|tile| *tile == Tile::Forest) {
+        return None;
+    }
+    trace!("Going from {:?} in direction {:?}", start, direction);
+    let mut walked = Corridor { tiles: vec![current], downhill: enterable(current, direction) };
+    while !is_junction(current) {
+        // a tile between junctions has exactly two neighbouring paths, one of them is where the walk came from
+        let (next, direction) = Direction::ALL.into_iter()
+            .map(|direction| (direction.offset(current), direction))
+            .find(|&(next, _)| next != previous && tiles.get(next).is_some_and(|tile| *tile != Tile::Forest))
+            .unwrap();
+        walked.downhill &= enterable(next, direction);
+        walked.tiles.push(next);
+        previous = current;
+        current = next;
+    }
+    Some(walked)
+}
+
+fn corridors(tiles: &Grid<Tile>, downhill: bool) -> Graph<usize> {
+    let mut junctions = if downhill { Graph::<usize>::directed() } else { Graph::<usize>::undirected() };
+    let positions = junction_positions(tiles);
+    for &position in &positions {
+        junctions.add_node(&junction_name(position));
+    }
+    for (i, &node) in positions.iter().enumerate() {
+        for d in Direction::ALL {
+            let Some(corridor) = corridor(tiles, node, d, |position| junctions.id(&junction_name(position)).is_some()) else {
+                continue;
+            };
+            let j = junctions.id(&junction_name(*corridor.tiles.last().unwrap())).unwrap();
+            // every corridor is walked from both ends, keep it once unless the way matters
+            if (i < j || (downhill && i != j)) && (!downhill || corridor.downhill) {
+                junctions.add_edge(i, j, corridor.tiles.len());
+            }
+        }
+    }
+    junctions
+}
+
+/// The hikes are tried one by one, and their number grows exponentially with the junctions. Puzzle maps have 36.
+const MAX_JUNCTIONS: usize = 40;
+
+/// The junctions, if there are few enough to try every hike over them.
+fn searchable(junctions: Graph<usize>) -> Result<Graph<usize>, SolveError> {
+    if junctions.len() > MAX_JUNCTIONS {
+        return Err(SolveError::new(format!("the map has {} junctions, too many to try every hike", junctions.len())));
+    }
+    if junctions.is_empty() {
+        return Err(SolveError::new("the map has no junctions"));
+    }
+    Ok(junctions)
+}
+
+fn no_hike() -> SolveError {
+    SolveError::new("no hike reaches the bottom row")
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Model = Grid<Tile>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tiles: &Grid<Tile>) -> Result<usize, SolveError> {
+        let junctions = searchable(downhill_junction_graph(tiles))?;
+        trace!("{}", junctions.to_dot());
+        longest_path(&junctions).ok_or_else(no_hike)
+    }
+
+    fn part2(tiles: &Grid<Tile>) -> Result<usize, SolveError> {
+        let junctions = searchable(junction_graph(tiles))?;
+        trace!("{}", junctions.to_dot());
+        longest_path(&junctions).ok_or_else(no_hike)
+    }
+}
+
+/// The forest in green and the slopes as arrows, with the longest hike of the part in yellow.
+impl Render for Day23 {
+    fn render(tiles: &Grid<Tile>, part: u8) -> Result<Grid<Cell>, SolveError> {
+        let downhill = part == 1;
+        let junctions = searchable(corridors(tiles, downhill))?;
+        let hike = longest_hike(&junctions).ok_or_else(no_hike)?;
+        let positions = junction_positions(tiles);
+        let mut picture = tiles.map(|tile| match tile {
+            Tile::Path => Cell::new(' ', Color::GREY),
+            Tile::Forest => Cell::new('#', Color::GREEN).on(Color(20, 60, 30)),
+            Tile::Slope(direction) => Cell::new(direction.arrow(), Color::WHITE),
+        });
+        let mut mark = |(x, y): (isize, isize)| picture[(x as usize, y as usize)].background = Color::YELLOW;
+        mark(positions[0]);
+        for pair in hike.windows(2) {
+            let (from, to) = (positions[pair[0]], positions[pair[1]]);
+            // of the corridors joining two junctions, the hike takes the longest
+            let corridor = Direction::ALL.into_iter()
+                .filter_map(|direction| corridor(tiles, from, direction, |position| positions.contains(&position)))
+                .filter(|corridor| corridor.tiles.last() == Some(&to) && (!downhill || corridor.downhill))
+                .max_by_key(|corridor| corridor.tiles.len())
+                .unwrap();
+            corridor.tiles.into_iter().for_each(&mut mark);
+        }
+        Ok(picture)
+    }
+}
+
+/// Like the puzzle's map, a `size` by `size` lattice of junctions, at least 2 by 2, joined by corridors of random
+/// lengths, with slopes at both ends of each corridor leading right or down. The path enters at the top left and
+/// leaves at the bottom right. The number of paths grows quickly with the size.
+impl Generator for Day23 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let junctions = size.max(2);
+        let lines = |rng: &mut Rng, first: usize| (0..junctions).scan(first, |line, i| {
+            if i > 0 {
+                *line += rng.between(2, 8) as usize;
+            }
+            Some(*line)
+        }).collect::<Vec<usize>>();
+        let columns = lines(rng, 1);
+        let first_row = rng.between(1, 6) as usize;
+        let rows = lines(rng, first_row);
+        let (width, height) = (columns[junctions - 1] + 2, rows[junctions - 1] + 2);
+        let mut tiles = Grid::filled(width, height, '#');
+        for y in 0..rows[0] {
+            tiles[(columns[0], y)] = '.';
+        }
+        tiles[(columns[junctions - 1], height - 1)] = '.';
+        for (i, &x) in columns.iter().enumerate() {
+            for (j, &y) in rows.iter().enumerate() {
+                tiles[(x, y)] = '.';
+                if let Some(&next) = columns.get(i + 1) {
+                    for corridor in x + 1..next {
+                        tiles[(corridor, y)] = if corridor == x + 1 || corridor == next - 1 { '>' } else { '.' };
+                    }
+                }
+                if let Some(&next) = rows.get(j + 1) {
+                    for corridor in y + 1..next {
+                        tiles[(x, corridor)] = if corridor == y + 1 || corridor == next - 1 { 'v' } else { '.' };
+                    }
+                }
+            }
+        }
+        tiles.to_string()
+    }
+}
@@ -0,0 +1,203 @@
+//! Day 24: Never Tell Me The Odds. Counts where the paths of hailstones cross in a test area, and finds the throw of
+//! a rock that hits every hailstone.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::debug;
+use regex::Regex;
+use std::cmp::Ordering;
+use f128::f128;
+use num_traits::{Float, ToPrimitive};
+
+/// The largest coordinate or velocity, which keeps the cross products of the rock's equations from overflowing.
+const MAX_VALUE: i128 = 1_000_000_000_000_000_000;
+
+/// A point or velocity as x, y and z.
+pub type V3 = [i128; 3];
+
+#[derive(Debug, Clone)]
+/// A hailstone, where it is now and how far it moves every nanosecond.
+pub struct Particle {
+    pub position: V3,
+    pub velocity: V3,
+    collision_time: f128,
+    collision_partner: usize,
+}
+
+impl Particle {
+    pub fn new(position: V3, velocity: V3) -> Particle {
+        Particle { position, velocity, collision_time: f128::MAX, collision_partner: usize::MAX }
+    }
+
+    fn parse(line: &str, start: Position) -> Result<Particle, ParseError> {
+        let particle_regex = Regex::new(r"^\s*-?\d+,\s*-?\d+,\s*-?\d+\s*@\s*-?\d+,\s*-?\d+,\s*-?\d+\s*$").unwrap();
+        parse::captures(&particle_regex, line, start)?;
+        let values = aoc_common::signed_numbers::<i128>(line, start)?;
+        if let Some(value) = values.iter().find(|value| value.abs() > MAX_VALUE) {
+            return Err(ParseError::new(start, &value.to_string(), "expected a value of at most 10^18 either way"));
+        }
+        Ok(Particle::new([values[0], values[1], values[2]], [values[3], values[4], values[5]]))
+    }
+
+    fn get_collision_time(&self, other: &Particle) -> (f128, f128) {
+        let t_1_divisor = self.velocity[0] * other.velocity[1] - self.velocity[1] * other.velocity[0];
+        if t_1_divisor == (0) {
+            return (f128::from(-1), f128::from(-1));
+        }
+        let a = f128::from( self.velocity[1]) /  f128::from( self.velocity[0]);
+        let c = f128::from( self.position[1]) - a * f128::from( self.position[0]);
+        let b = f128::from(other.velocity[1]) / f128::from(other.velocity[0]);
+        let d = f128::from(other.position[1]) - b * f128::from(other.position[0]);
+        let p1 = (d-c)/(a-b);
+        let t1 = (p1-f128::from( self.position[0])) / f128::from( self.velocity[0]);
+        let t2 = (p1-f128::from(other.position[0])) / f128::from(other.velocity[0]);
+        (t1, t2)
+    }
+}
+
+/// Parses one `px, py, pz @ vx, vy, vz` hailstone per line, at least three of them.
+pub fn parse(input: &str) -> Result<Vec<Particle>, ParseError> {
+    let lines = aoc_common::numbered_lines(input);
+    if lines.len() < 3 {
+        return Err(ParseError::new(Position::start_of(lines.len() + 1), "", "expected at least three hailstones"));
+    }
+
+    lines.iter().map(|(number, line)| Particle::parse(line, Position::start_of(*number))).collect()
+}
+
+
+fn cross(a: V3, b: V3) -> V3 {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn difference(a: V3, b: V3) -> V3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// For the rock at `p` with velocity `v`, `(p_i - p) x (v_i - v) = 0` holds for every hailstone. Subtracting this for two
+/// hailstones removes the non-linear `p x v`, leaving three linear equations in the six unknowns.
+fn rock_equations(a: &Particle, b: &Particle) -> Vec<[i128; 7]> {
+    let d = difference(a.velocity, b.velocity);
+    let e = difference(a.position, b.position);
+    let rhs = difference(cross(a.position, a.velocity), cross(b.position, b.velocity));
+    vec![
+        [0, d[2], -d[1], 0, -e[2], e[1], rhs[0]],
+        [-d[2], 0, d[0], e[2], 0, -e[0], rhs[1]],
+        [d[1], -d[0], 0, -e[1], e[0], 0, rhs[2]],
+    ]
+}
+
+/// Gaussian elimination with partial pivoting, rounding the solution to integers. `None` if the equations have no
+/// single solution, or it does not fit.
+fn solve(equations: &[[i128; 7]]) -> Option<Vec<i128>> {
+    let mut rows = equations.iter().map(|row| row.map(f128::from)).collect::<Vec<_>>();
+    let n = rows.len();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|i, j| rows[*i][column].abs().partial_cmp(&rows[*j][column].abs()).unwrap_or(Ordering::Equal)).unwrap();
+        if rows[pivot][column] == f128::from(0) {
+            return None;
+        }
+        rows.swap(column, pivot);
+        let pivot_row = rows[column];
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == column {
+                continue;
+            }
+            let factor = row[column] / pivot_row[column];
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    (0..n).map(|i| (rows[i][n] / rows[i][i]).round().to_i128()).collect()
+}
+
+/// The number of pairs of hailstones whose paths cross at x and y from `low` to `high`, ignoring z, where both
+/// hailstones still have to pass.
+pub fn future_crossings(particles: &[Particle], low: i128, high: i128) -> usize {
+    let mut particles = particles.to_vec();
+    let mut intersections = 0;
+
+    for i in 0..particles.len() {
+        for j in (i+1)..particles.len() {
+            let (t1, t2) = particles[i].get_collision_time(&particles[j]);
+            if t1 < f128::from(0) || t2 < f128::from(0) {
+                continue;
+            }
+            if t1 < particles[i].collision_time && t2 < particles[j].collision_time {
+                particles[i].collision_time = t1;
+                particles[i].collision_partner = j;
+                particles[j].collision_time = t2;
+                particles[j].collision_partner = i;
+            }
+            let position = (0..2).map(|index| f128::from(particles[i].position[index]) + t1 * f128::from(particles[i].velocity[index])).collect::<Vec<f128>>();
+            let mut in_bounds = true;
+            for pos in &position {
+                if *pos < f128::from(low) || *pos > f128::from(high) {
+                    in_bounds = false;
+                }
+            }
+            if !in_bounds {
+                continue;
+            }
+            intersections += 1;
+        }
+    }
+    intersections
+}
+
+/// Where a rock has to be thrown from, and how fast, to hit every hailstone: x, y and z, then the velocity. The first
+/// three hailstones are enough to tell, unless they fly in ways that leave more than one throw, or none.
+pub fn rock_position(particles: &[Particle]) -> Option<Vec<i128>> {
+    let mut equations = rock_equations(&particles[0], &particles[1]);
+    equations.append(&mut rock_equations(&particles[0], &particles[2]));
+    solve(&equations)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Model = Vec<Particle>;
+    type Answer1 = usize;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Vec<Particle>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(particles: &Vec<Particle>) -> Result<usize, SolveError> {
+        Ok(future_crossings(particles, 200000000000000, 400000000000000))
+    }
+
+    fn part2(particles: &Vec<Particle>) -> Result<i128, SolveError> {
+        let rock = rock_position(particles).ok_or_else(|| SolveError::new("the first three hailstones do not tell a single throw"))?;
+        debug!("rock: {rock:?}");
+        rock[..3].iter().try_fold(0_i128, |sum, coordinate| sum.checked_add(*coordinate))
+            .ok_or_else(|| SolveError::new("the sum of the rock's coordinates overflows"))
+    }
+}
+
+/// `size` hailstones, at least three, around the test area of part 1, all hit by a rock thrown from whole numbers at
+/// whole numbers of nanoseconds. No two hailstones fly at the same velocity, and none stands still along x.
+impl Generator for Day24 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let velocity = |rng: &mut Rng| [(); 3].map(|_| {
+            let speed = rng.between(1, 300);
+            if rng.chance(0.5) { -speed } else { speed }
+        });
+        let rock_position = [(); 3].map(|_| rng.between(250_000_000_000_000, 350_000_000_000_000));
+        let rock_velocity = velocity(rng);
+        let mut velocities = std::collections::HashSet::<[i64; 3]>::new();
+        let mut input = String::new();
+        while velocities.len() < size.max(3) {
+            let hailstone_velocity = velocity(rng);
+            if hailstone_velocity == rock_velocity || !velocities.insert(hailstone_velocity) {
+                continue;
+            }
+            let time = rng.between(10_000_000_000, 500_000_000_000);
+            let [x, y, z] = [0, 1, 2].map(|i| rock_position[i] + time * (rock_velocity[i] - hailstone_velocity[i]));
+            let [vx, vy, vz] = hailstone_velocity;
+            input += &format!("{x}, {y}, {z} @ {vx}, {vy}, {vz}\n");
+        }
+        input
+    }
+}
@@ -0,0 +1,137 @@
+//! Day 25: Snowverload. Finds the three wires that split the components into two groups, and multiplies the sizes
+//! of the groups.
+
+use aoc_common::{parse, Generator, Graph, ParseError, Position, Rng, Solution, SolveError};
+use log::{debug, trace};
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// Parses one `name: other other` line per component, wiring it to each of the others.
+pub fn parse(input: &str) -> Result<Graph, ParseError> {
+    let line_regex = Regex::new(r"^\w+: \w+( \w+)*$").unwrap();
+    let mut graph = Graph::undirected();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        parse::captures(&line_regex, line, Position::start_of(number))?;
+        let (from, to) = line.split_once(": ").unwrap();
+        for to in to.split(' ') {
+            graph.connect(from, to, ());
+        }
+    }
+    trace!("{}", graph.to_dot());
+    Ok(graph)
+}
+
+/// Finds a path from `source` to `sink` through edges that still have capacity left, treating every wire as a
+/// unit capacity edge in both directions. Returns the nodes reachable from `source` if there is no such path.
+fn augment(graph: &Graph, flow: &mut HashMap<(usize, usize), i32>, source: usize, sink: usize) -> Result<(), HashSet<usize>> {
+    let mut previous = HashMap::<usize, usize>::new();
+    let mut visited = HashSet::from([source]);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        if node == sink {
+            let mut current = sink;
+            while current != source {
+                let from = previous[&current];
+                *flow.entry((from, current)).or_insert(0) += 1;
+                *flow.entry((current, from)).or_insert(0) -= 1;
+                current = from;
+            }
+            return Ok(());
+        }
+        for &(next, ()) in graph.neighbours(node) {
+            if flow.get(&(node, next)).copied().unwrap_or(0) < 1 && visited.insert(next) {
+                previous.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    Err(visited)
+}
+
+/// Cuts the three wires separating the graph and returns the sizes of both halves, or `None` if no three wires do.
+/// Any node that is connected to the first node by exactly three independent paths has to be in the other half.
+pub fn split(graph: &Graph) -> Option<(usize, usize)> {
+    for sink in 1..graph.len() {
+        let mut flow = HashMap::<(usize, usize), i32>::new();
+        for paths in 0.. {
+            if let Err(reachable) = augment(graph, &mut flow, 0, sink) {
+                if paths == 3 {
+                    debug!("cutting {} from {}", graph.name(0), graph.name(sink));
+                    return Some((reachable.len(), graph.len() - reachable.len()));
+                }
+                break;
+            }
+            if paths == 3 {
+                break;
+            }
+        }
+    }
+    None
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Model = Graph;
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        parse(input)
+    }
+
+    fn part1(graph: &Graph) -> Result<usize, SolveError> {
+        let (a, b) = split(graph).ok_or_else(|| SolveError::new("no three wires split the components in two"))?;
+        Ok(a * b)
+    }
+
+    /// Day 25 has no second puzzle.
+    fn part2(_graph: &Graph) -> Result<&'static str, SolveError> {
+        Ok("Merry Christmas!")
+    }
+}
+
+/// `size` components, at least ten, in two groups of at least five that are joined by exactly three wires. Within a
+/// group every component is wired to the two before and after it in a ring, and then some, so cutting fewer than four
+/// wires never splits a group.
+impl Generator for Day25 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(10);
+        let first = rng.between(5, size as i64 - 5) as usize;
+        let mut wires = std::collections::BTreeSet::<(usize, usize)>::new();
+        let mut wire = |a: usize, b: usize| a != b && wires.insert((a.min(b), a.max(b)));
+        for (start, length) in [(0, first), (first, size - first)] {
+            for i in 0..length {
+                wire(start + i, start + (i + 1) % length);
+                wire(start + i, start + (i + 2) % length);
+            }
+            for _ in 0..length / 2 {
+                wire(start + rng.below(length), start + rng.below(length));
+            }
+        }
+        let mut cut = 0;
+        while cut < 3 {
+            if wire(rng.below(first), first + rng.below(size - first)) {
+                cut += 1;
+            }
+        }
+
+        let mut names = std::collections::HashSet::<String>::new();
+        let names = (0..size).map(|_| loop {
+            let name = rng.word(3);
+            if names.insert(name.clone()) {
+                break name;
+            }
+        }).collect::<Vec<_>>();
+        let mut connections = std::collections::BTreeMap::<usize, Vec<&str>>::new();
+        for (a, b) in wires {
+            let (from, to) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+            connections.entry(from).or_default().push(&names[to]);
+        }
+        let mut lines = connections.iter().map(|(from, to)| format!("{}: {}", names[*from], to.join(" "))).collect::<Vec<_>>();
+        rng.shuffle(&mut lines);
+        lines.join("\n") + "\n"
+    }
+}
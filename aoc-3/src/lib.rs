@@ -0,0 +1,118 @@
+//! Day 3: Gear Ratios. Finds the part numbers next to symbols in an engine schematic, and the gears between two of them.
+
+use aoc_common::{parse, Generator, Grid, ParseError, Position, Rng, Solution, SolveError};
+use log::{debug, trace};
+use std::collections::BTreeSet;
+
+/// Anything but a digit or `.` is a symbol.
+pub fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+/// What the solvers need from a schematic.
+pub struct Schematic {
+    /// Every number next to a symbol.
+    pub part_numbers: Vec<usize>,
+    /// `(y, x, number)` for every `*` at `(x, y)` and each number next to it.
+    pub potential_gears: Vec<(usize, usize, usize)>,
+}
+
+/// Parses the schematic grid, finding the numbers next to symbols and the `*` next to numbers.
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let grid = Grid::from_str(input, Some)?;
+    let mut part_numbers: Vec<usize> = Vec::new();
+    let mut potential_gears: Vec<(usize, usize, usize)> = Vec::new();
+    for y in 0..grid.height() {
+        let row = grid.row(y);
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            let text = row[start..x].iter().collect::<String>();
+            let number_value = parse::parse_at::<usize>(&text, Position::new(y + 1, start + 1))?;
+            let neighbours = (start..x).flat_map(|x| grid.neighbours8((x, y))).collect::<BTreeSet<(usize, usize)>>();
+            if neighbours.iter().any(|position| is_symbol(grid[*position])) {
+                trace!("part: {}: {} in interval ({}, {})", y, number_value, start, x);
+                part_numbers.push(number_value);
+            }
+            for &(gear_x, gear_y) in neighbours.iter().filter(|position| grid[**position] == '*') {
+                potential_gears.push((gear_y, gear_x, number_value));
+            }
+        }
+    }
+    debug!("{} potential gears", potential_gears.len());
+    Ok(Schematic { part_numbers, potential_gears })
+}
+
+/// Sums the products of the two numbers next to every `*` that has exactly two.
+pub fn gear_ratio_sum(potential_gears: &[(usize, usize, usize)]) -> usize {
+    let mut sum2 = 0;
+    for (i, (line, column, number_value)) in potential_gears.iter().enumerate() {
+        trace!("potential gear at {}, {}: {}", line, column, number_value);
+        let mut neighbour_count = 0;
+        let mut neighbour_value = 0;
+        for (other_line, other_column, other_number_value) in potential_gears.iter().skip(i + 1) {
+            if line == other_line && column == other_column {
+                neighbour_count += 1;
+                neighbour_value = *other_number_value;
+            }
+        }
+        if neighbour_count == 1 {
+            trace!("found a gear at {}, {}: {}", line, column, neighbour_value * number_value);
+            sum2 += neighbour_value * number_value;
+        }
+    }
+    sum2
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Model = Schematic;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Schematic, ParseError> {
+        parse(input)
+    }
+
+    fn part1(schematic: &Schematic) -> Result<usize, SolveError> {
+        Ok(schematic.part_numbers.iter().sum())
+    }
+
+    fn part2(schematic: &Schematic) -> Result<usize, SolveError> {
+        Ok(gear_ratio_sum(&schematic.potential_gears))
+    }
+}
+
+/// A `size` by `size` schematic of numbers of up to three digits, with symbols and gears scattered between them.
+impl Generator for Day3 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const SYMBOLS: [char; 10] = ['*', '*', '#', '+', '$', '/', '@', '=', '%', '&'];
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut row = String::new();
+            while row.len() < size {
+                let digits = rng.below(3) + 1;
+                if row.len() + digits < size && rng.chance(0.2) {
+                    row += &rng.between(10i64.pow(digits as u32 - 1), 10i64.pow(digits as u32) - 1).to_string();
+                    // keeps the number from running into the next one
+                    row.push('.');
+                } else if rng.chance(0.1) {
+                    row.push(*rng.choose(&SYMBOLS));
+                } else {
+                    row.push('.');
+                }
+            }
+            input += &row;
+            input.push('\n');
+        }
+        input
+    }
+}
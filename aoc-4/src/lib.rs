@@ -0,0 +1,92 @@
+//! Day 4: Scratchcards. Scores cards by their winning numbers, and counts the copies they win of the cards below.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+use regex::Regex;
+use std::cmp::min;
+
+/// Parses the cards into the number of winning numbers each of them has.
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let card: Regex = Regex::new(r"^Card\s*(\d+):(?<winning>[\d ]*)\|(?<values>[\d ]*)$").unwrap();
+    let mut matches = Vec::<usize>::new();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let start = Position::start_of(number);
+        let card_result = parse::captures(&card, line, start)?;
+        let winning = card_result.name("winning").unwrap();
+        let winning_numbers = aoc_common::numbers::<u32>(winning.as_str(), start.at_byte(line, winning.start()))?;
+        let values_match = card_result.name("values").unwrap();
+        let values = aoc_common::numbers::<u32>(values_match.as_str(), start.at_byte(line, values_match.start()))?;
+        let mut number_of_matches = 0;
+        for value in &values {
+            if winning_numbers.contains(value) {
+                number_of_matches += 1;
+            }
+        }
+        trace!("card {}: winning: {}, values: {}, {} matches", &card_result[1], winning_numbers.len(), values.len(), number_of_matches);
+        matches.push(number_of_matches);
+    }
+    Ok(matches)
+}
+
+/// The total number of cards once every card has won copies of the next ones, one for each of its `matches`.
+pub fn total_cards(matches: &[usize]) -> usize {
+    let number_of_original_cards = matches.len();
+    let mut number_of_cards: Vec<usize> = std::iter::repeat_n(1, number_of_original_cards).collect();
+    for (card_index, number_of_matches) in matches.iter().enumerate() {
+        let card_number = card_index + 1;
+        for i in card_number..min(card_number+number_of_matches, number_of_original_cards) {
+            number_of_cards[i] += number_of_cards[card_number - 1];
+        }
+    }
+    let mut sum2 = 0;
+    for cards in number_of_cards {
+        trace!("{cards}");
+        sum2 += cards;
+    }
+    sum2
+}
+
+/// The points of all cards: 1 for the first match, doubled for each further one.
+pub fn points(matches: &[usize]) -> usize {
+    matches.iter().map(|number_of_matches| if *number_of_matches == 0 { 0 } else { 1 << (number_of_matches - 1) }).sum()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Model = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(matches: &Vec<usize>) -> Result<usize, SolveError> {
+        Ok(points(matches))
+    }
+
+    fn part2(matches: &Vec<usize>) -> Result<usize, SolveError> {
+        Ok(total_cards(matches))
+    }
+}
+
+/// `size` cards of ten winning numbers and 25 numbers we have. Most cards win nothing, so the number of copies in
+/// part 2 stays far from overflowing however many cards there are.
+impl Generator for Day4 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.to_string().len();
+        let mut input = String::new();
+        for card in 1..=size {
+            let mut numbers = (1..100).collect::<Vec<usize>>();
+            rng.shuffle(&mut numbers);
+            let matches = if rng.chance(0.7) { 0 } else if rng.chance(0.95) { rng.below(4) + 1 } else { rng.below(10) + 1 };
+            let (winning, rest) = numbers.split_at(10);
+            let mut ours = winning[..matches].iter().chain(&rest[..25 - matches]).copied().collect::<Vec<_>>();
+            rng.shuffle(&mut ours);
+            let line = |numbers: &[usize]| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+            input += &format!("Card {card:>width$}: {} | {}\n", line(winning), line(&ours));
+        }
+        input
+    }
+}
@@ -0,0 +1,156 @@
+//! Day 5: If You Give A Seed A Fertilizer. Maps seeds through the almanac's chain of ranges to their locations,
+//! either one by one or as whole ranges at once.
+
+use aoc_common::{Generator, Interval, IntervalSet, ParseError, Position, Rng, Solution, SolveError};
+use log::{debug, trace};
+
+/// Maps a key through one step of the almanac.
+pub fn translate(key: u64, map: &[(u64, u64, u64)]) -> u64 {
+    for (destination_start, source_start, length) in map {
+        if (*source_start..(source_start + length)).contains(&key) {
+            return destination_start + (key - source_start);
+        }
+    }
+    key
+}
+
+/// Maps a set of keys through one step of the almanac. Each key is translated by the first entry whose source range
+/// contains it, keys outside of every source range keep their number.
+pub fn translate_set(keys: &IntervalSet, map: &[(u64, u64, u64)]) -> IntervalSet {
+    let mut unmapped = keys.clone();
+    let mut mapped = IntervalSet::new();
+    for &(destination_start, source_start, length) in map {
+        let source = IntervalSet::from(Interval::new(source_start as i64, (source_start + length) as i64));
+        let translated = unmapped.intersection(&source).offset(destination_start as i64 - source_start as i64);
+        trace!("{:?} -> {:?}", unmapped.intersection(&source), translated);
+        mapped = mapped.union(&translated);
+        unmapped = unmapped.difference(&source);
+    }
+    mapped.union(&unmapped)
+}
+
+pub struct Almanac {
+    /// The numbers after `seeds:`, single seeds in part 1 and pairs of start and length in part 2.
+    pub seeds: Vec<u64>,
+    /// The maps in order, each a list of `(destination start, source start, length)`.
+    pub steps: Vec<Vec<(u64, u64, u64)>>,
+}
+
+/// The largest number in the almanac, so that a start and a length add up to no more than an `Interval` holds.
+pub const MAX_NUMBER: u64 = 1 << 60;
+
+fn numbers(text: &str, position: Position) -> Result<Vec<u64>, ParseError> {
+    let numbers = aoc_common::numbers::<u64>(text, position)?;
+    // the numbers are the runs of digits, in order
+    let starts = text.char_indices().filter(|&(i, c)| c.is_ascii_digit() && !text[..i].ends_with(|c: char| c.is_ascii_digit()));
+    if let Some((number, (start, _))) = numbers.iter().zip(starts).find(|(&number, _)| number > MAX_NUMBER) {
+        return Err(ParseError::new(position.at_byte(text, start), &number.to_string(), "expected a number of at most 2^60"));
+    }
+    Ok(numbers)
+}
+
+/// Parses the seeds and the maps, which are told apart by their headers and not by their names.
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let lines = aoc_common::numbered_lines(input);
+
+    let Some((seed_line, seed_text)) = lines.first() else {
+        return Err(ParseError::new(Position::start_of(1), "", "expected a list of seeds"));
+    };
+    let seeds = numbers(seed_text, Position::start_of(*seed_line))?;
+
+    let mut steps: Vec<Vec<(u64, u64, u64)>> = Vec::new();
+    let mut current_map: Vec<(u64, u64, u64)> = Vec::new();
+
+    for (number, line) in &lines[1..] {
+        let numbers_in_line = numbers(line, Position::start_of(*number))?;
+        if numbers_in_line.is_empty() {
+            if !current_map.is_empty() {
+                debug!("collected {} entries", current_map.len());
+                steps.push(current_map.clone());
+            }
+            trace!("reading {}", line);
+            current_map = Vec::new();
+            continue;
+        }
+        let [destination, source, length] = numbers_in_line[..] else {
+            return Err(ParseError::new(Position::start_of(*number), line, "expected destination, source and length"));
+        };
+        current_map.push((destination, source, length));
+    }
+    if !current_map.is_empty() {
+        debug!("collected {} entries", current_map.len());
+        steps.push(current_map.clone());
+    }
+    Ok(Almanac { seeds, steps })
+}
+
+/// The lowest location of any of the seeds, `None` if there are none.
+pub fn lowest_location(almanac: &Almanac) -> Option<u64> {
+    almanac.seeds.iter().map(|&seed| almanac.steps.iter().fold(seed, |value, step| translate(value, step))).min()
+}
+
+/// The lowest location of any seed in the seed ranges, `None` if they are all empty. A last seed number without a
+/// length is left out.
+pub fn lowest_range_location(almanac: &Almanac) -> Option<u64> {
+    let mut current = almanac.seeds.chunks_exact(2)
+        .map(|x| Interval::new(x[0] as i64, (x[0] + x[1]) as i64))
+        .collect::<IntervalSet>();
+    trace!("seed ranges: {:?}", current);
+    for step in &almanac.steps {
+        current = translate_set(&current, step);
+        trace!("after step: {:?}", current);
+    }
+    current.min().map(|location| location as u64)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Model = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        parse(input)
+    }
+
+    fn part1(almanac: &Almanac) -> Result<u64, SolveError> {
+        lowest_location(almanac).ok_or_else(|| SolveError::new("there are no seeds"))
+    }
+
+    fn part2(almanac: &Almanac) -> Result<u64, SolveError> {
+        if !almanac.seeds.len().is_multiple_of(2) {
+            return Err(SolveError::new(format!("the {} seed numbers do not pair up into ranges", almanac.seeds.len())));
+        }
+        lowest_range_location(almanac).ok_or_else(|| SolveError::new("every seed range is empty"))
+    }
+}
+
+/// `size` seed ranges and seven maps of `size` entries each, with numbers below 2^32. The source ranges of a map
+/// never overlap, and now and then a seed range or map entry is empty.
+impl Generator for Day5 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+        const LIMIT: i64 = 1 << 32;
+        let length = |rng: &mut Rng, most: i64| if rng.chance(0.05) { 0 } else { rng.between(0, most) };
+        let seeds = (0..size)
+            .map(|_| {
+                let start = rng.between(0, LIMIT - 1);
+                format!("{start} {}", length(rng, (LIMIT - start).min(LIMIT / size as i64)))
+            })
+            .collect::<Vec<_>>();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for names in CATEGORIES.windows(2) {
+            input += &format!("\n{}-to-{} map:\n", names[0], names[1]);
+            let mut starts = (0..size).map(|_| rng.between(0, LIMIT - 1)).collect::<Vec<_>>();
+            starts.sort();
+            starts.dedup();
+            starts.push(LIMIT);
+            for bounds in starts.windows(2) {
+                let length = length(rng, bounds[1] - bounds[0]);
+                input += &format!("{} {} {length}\n", rng.between(0, LIMIT - length), bounds[0]);
+            }
+        }
+        input
+    }
+}
@@ -0,0 +1,99 @@
+//! Day 6: Wait For It. Counts the ways to win boat races by holding the button for the right time.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::debug;
+
+/// The number of whole milliseconds to hold the button for that beat `target_distance` in a race of `time`. The
+/// distance rises up to holding for half the race and falls after, so the winning times are found by bisection.
+pub fn count_winning_times(time: usize, target_distance: usize) -> usize {
+    let distance = |held: usize| held as u128 * (time - held) as u128;
+    let half = time / 2;
+    if distance(half) <= target_distance as u128 {
+        return 0;
+    }
+    let (mut losing, mut winning) = (0, half);
+    while winning - losing > 1 {
+        let middle = (losing + winning) / 2;
+        if distance(middle) > target_distance as u128 {
+            winning = middle;
+        } else {
+            losing = middle;
+        }
+    }
+    time - 2 * winning + 1
+}
+
+pub struct Races {
+    pub times: Vec<usize>,
+    /// The record distance of each race.
+    pub distances: Vec<usize>,
+    /// The one race of part 2, read with the spaces between the numbers left out.
+    pub combined_time: usize,
+    pub combined_distance: usize,
+}
+
+/// Parses the `Time:` and `Distance:` lines, both as separate races and as one.
+pub fn parse(input: &str) -> Result<Races, ParseError> {
+    let lines = aoc_common::numbered_lines(input);
+    let [(time_line, time_text), (distance_line, distance_text)] = lines[..] else {
+        return Err(ParseError::new(Position::start_of(1), input.lines().next().unwrap_or(""), "expected a line of times and a line of distances"));
+    };
+    let times = aoc_common::numbers::<usize>(time_text, Position::start_of(time_line))?;
+    let distances = aoc_common::numbers::<usize>(distance_text, Position::start_of(distance_line))?;
+    if times.len() != distances.len() {
+        return Err(ParseError::new(Position::start_of(distance_line), distance_text, format!("expected {} distances", times.len())));
+    }
+    let combined = |text: &str, line: usize| -> Result<usize, ParseError> {
+        let digits = text.chars().filter(char::is_ascii_digit).collect::<String>();
+        parse::parse_at(&digits, Position::start_of(line))
+    };
+    Ok(Races {
+        combined_time: combined(time_text, time_line)?,
+        combined_distance: combined(distance_text, distance_line)?,
+        times,
+        distances,
+    })
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Model = Races;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Races, ParseError> {
+        parse(input)
+    }
+
+    fn part1(races: &Races) -> Result<usize, SolveError> {
+        let scores = races.times.iter().zip(races.distances.iter()).map(|(x, y)| (*x, *y)).collect::<Vec<(usize, usize)>>();
+        let mut product: usize = 1;
+        for (time, target_distance) in scores {
+            product = product.checked_mul(count_winning_times(time, target_distance)).ok_or_else(|| SolveError::new("the product of the ways to win overflows"))?;
+        }
+        Ok(product)
+    }
+
+    fn part2(races: &Races) -> Result<usize, SolveError> {
+        debug!("{} -> {}", races.combined_time, races.combined_distance);
+        Ok(count_winning_times(races.combined_time, races.combined_distance))
+    }
+}
+
+/// `size` races, but no more than four: part 2 counts every way to race the combined time, which grows a hundredfold
+/// with each race. Every race can be won.
+impl Generator for Day6 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let races = (0..size.clamp(1, 4))
+            .map(|_| {
+                let time = rng.between(7, 99);
+                (time, rng.between(0, time * time / 4 - 1))
+            })
+            .collect::<Vec<_>>();
+        let row = |name: &str, values: Vec<i64>| {
+            format!("{name}{}\n", values.iter().map(|value| format!("{value:>5}")).collect::<String>())
+        };
+        row("Time:    ", races.iter().map(|race| race.0).collect()) + &row("Distance:", races.iter().map(|race| race.1).collect())
+    }
+}
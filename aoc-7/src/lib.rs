@@ -0,0 +1,334 @@
+//! Day 7: Camel Cards. Ranks poker-like hands by type and then card by card, with or without jokers.
+
+use aoc_common::{parse, Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::{debug, trace};
+use regex::Regex;
+use std::fmt::Display;
+use std::cmp;
+use std::ops::Index;
+
+/// A card, ordered by strength when jokers are wild.
+#[derive(Ord, Eq, PartialEq, PartialOrd, Debug, Copy, Clone)]
+pub enum Card {
+    CJ = 1,
+    C2 = 2,
+    C3 = 3,
+    C4 = 4,
+    C5 = 5,
+    C6 = 6,
+    C7 = 7,
+    C8 = 8,
+    C9 = 9,
+    CT = 11,
+    CQ = 12,
+    CK = 13,
+    CA = 14,
+}
+
+impl Card {
+    /// The card written as `c`.
+    pub fn get(c: char) -> Option<Card> {
+        Some(match c {
+            '2' => Card::C2,
+            '3' => Card::C3,
+            '4' => Card::C4,
+            '5' => Card::C5,
+            '6' => Card::C6,
+            '7' => Card::C7,
+            '8' => Card::C8,
+            '9' => Card::C9,
+            'T' => Card::CT,
+            'J' => Card::CJ,
+            'Q' => Card::CQ,
+            'K' => Card::CK,
+            'A' => Card::CA,
+            _ => return None,
+        })
+    }
+
+    // jokers are the weakest card when they are wild and sit between T and Q otherwise
+    fn strength(&self, jokers: bool) -> u8 {
+        match self {
+            Card::CJ if !jokers => 10,
+            _ => *self as u8,
+        }
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Card::C2 => "2",
+                Card::C3 => "3",
+                Card::C4 => "4",
+                Card::C5 => "5",
+                Card::C6 => "6",
+                Card::C7 => "7",
+                Card::C8 => "8",
+                Card::C9 => "9",
+                Card::CT => "T",
+                Card::CJ => "J",
+                Card::CQ => "Q",
+                Card::CK => "K",
+                Card::CA => "A",
+            }
+        ).expect("Failed to write card");
+        Ok(())
+    }
+}
+
+#[derive(Eq)]
+struct CardCollection {vector: Vec<CardEntry> }
+
+impl CardCollection {
+    fn len(&self) -> usize {
+        self.vector.len()
+    }
+}
+
+impl PartialEq for CardCollection {
+    fn eq(&self, other: &CardCollection) -> bool {
+        self.vector == other.vector
+    }
+}
+
+impl Index<usize> for CardCollection {
+    type Output = CardEntry;
+    fn index(&self, index: usize) -> &CardEntry {
+        &self.vector[index]
+    }
+}
+
+impl CardCollection {
+    fn from(cards: &[Card], jokers: bool) -> CardCollection {
+        CardCollection { vector: CardEntry::from(cards, jokers) }
+    }
+}
+
+#[derive(Eq)]
+struct CardEntry {
+    card: Card,
+    count: usize,
+}
+
+// sorting goes through `partial_cmp`, which orders in reverse
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for CardEntry {
+    fn partial_cmp(&self, other: &CardEntry) -> Option<cmp::Ordering> {
+        Some(other.cmp(self))
+    }
+}
+
+impl Ord for CardEntry {
+    fn cmp(&self, other: &CardEntry) -> cmp::Ordering {
+        if self.count > other.count {
+            cmp::Ordering::Greater
+        } else if self.count < other.count {
+            cmp::Ordering::Less
+        } else {
+            self.card.cmp(&other.card)
+        }
+    }
+}
+
+impl PartialEq for CardEntry {
+    fn eq(&self, other: &CardEntry) -> bool {
+        self.card == other.card && self.count == other.count
+    }
+}
+
+
+impl CardEntry {
+    fn new(card: Card, count: usize) -> CardEntry {
+        CardEntry { card, count }
+    }
+
+    fn from(cards: &[Card], jokers: bool) -> Vec<CardEntry> {
+        let mut result: Vec<CardEntry> = Vec::new();
+        let mut joker_count = 0;
+        for card in cards {
+            if jokers && *card == Card::CJ {
+                joker_count += 1;
+                continue;
+            }
+            if let Some(entry) = result.iter_mut().find(|x| x.card == *card) {
+                entry.count += 1;
+            } else {
+                result.push(CardEntry::new(*card, 1));
+            }
+        }
+        result.sort();
+        if result.is_empty() {
+            result.push(CardEntry::new(Card::CJ, 0));
+        }
+        result[0].count += joker_count;
+        result
+    }
+}
+
+#[derive(Eq)]
+struct Hand {
+    cards: CardCollection,
+    card_vector: Vec<Card>,
+    bid: usize,
+    jokers: bool,
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Hand) -> Option<cmp::Ordering> {
+        Some(other.cmp(self))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Hand) -> cmp::Ordering {
+        if self.cards[0].count > other.cards[0].count {
+            cmp::Ordering::Greater
+        } else if self.cards[0].count < other.cards[0].count {
+            cmp::Ordering::Less
+        } else {
+            if self.cards.len() == 1 {
+                self.compare_cards(other)
+            } else if self.cards[1].count > other.cards[1].count {
+                cmp::Ordering::Greater
+            } else if self.cards[1].count < other.cards[1].count {
+                cmp::Ordering::Less
+            } else {
+                let result = self.compare_cards(other);
+                if result == cmp::Ordering::Equal {
+                    debug!("found some duplicates!");
+                }
+                result
+            }
+        }
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Hand) -> bool {
+        self.cards == other.cards
+    }
+}
+
+impl Hand {
+    fn new(play: &Play, jokers: bool) -> Hand {
+        let card_collection = CardCollection::from(&play.cards, jokers);
+        Hand { cards: card_collection, card_vector: play.cards.clone(), bid: play.bid, jokers }
+    }
+
+    fn compare_cards(&self, other: &Hand) -> cmp::Ordering {
+        for i in 0..5 {
+            let strength = self.card_vector[i].strength(self.jokers);
+            let other_strength = other.card_vector[i].strength(other.jokers);
+            if strength != other_strength {
+                return strength.cmp(&other_strength);
+            }
+        }
+        cmp::Ordering::Equal
+    }
+}
+
+impl Display for CardCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.vector.iter().for_each(|entry| {
+            let card = &entry.card;
+            let count = &entry.count;
+            for _ in 0..*count {
+                write!(f, "{},", *card).expect("Failed to write CardCollection");
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cards: {:?} ({}) Bid: {:0>3}", self.card_vector, self.cards, self.bid).expect("Failed to write Hand");
+        Ok(())
+    }
+}
+
+/// Sums every bid times the rank of its hand, the weakest hand having rank 1. With `jokers`, J is the weakest card
+/// but stands in for whichever card makes the best hand. `None` if the sum does not fit.
+pub fn total_score(plays: &[Play], jokers: bool) -> Option<usize> {
+    let mut hands: Vec<Hand> = plays.iter().map(|play| Hand::new(play, jokers)).collect();
+    hands.sort();
+    hands.reverse();
+    let mut total_score: usize = 0;
+    for (i, hand) in hands.iter().enumerate() {
+        let score = hand.bid.checked_mul(i + 1)?;
+        trace!("{} -> {}", hand, score);
+        total_score = total_score.checked_add(score)?;
+    }
+    Some(total_score)
+}
+
+/// A hand of five cards and its bid.
+pub struct Play {
+    pub cards: Vec<Card>,
+    pub bid: usize,
+}
+
+/// Parses one hand and bid per line.
+pub fn parse(input: &str) -> Result<Vec<Play>, ParseError> {
+    let play_regex = Regex::new(r"^(?<cards>\S+)\s+(?<bid>\d+)$").unwrap();
+    let mut plays = Vec::<Play>::new();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let start = Position::start_of(number);
+        let result = parse::captures(&play_regex, line, start)?;
+        let cards_text = &result["cards"];
+        let cards = cards_text.chars().enumerate().map(|(i, c)| {
+            Card::get(c).ok_or_else(|| ParseError::new(Position::new(number, i + 1), &c.to_string(), "invalid card"))
+        }).collect::<Result<Vec<Card>, ParseError>>()?;
+        if cards.len() != 5 {
+            return Err(ParseError::new(start, cards_text, "expected 5 cards"));
+        }
+        let bid = parse::parse_group(&result, "bid", line, start)?;
+        plays.push(Play { cards, bid });
+    }
+    Ok(plays)
+}
+
+fn overflow() -> SolveError {
+    SolveError::new("the total winnings overflow")
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Model = Vec<Play>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Play>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(plays: &Vec<Play>) -> Result<usize, SolveError> {
+        total_score(plays, false).ok_or_else(overflow)
+    }
+
+    fn part2(plays: &Vec<Play>) -> Result<usize, SolveError> {
+        total_score(plays, true).ok_or_else(overflow)
+    }
+}
+
+/// `size` different hands with bids up to 1000, all jokers now and then.
+impl Generator for Day7 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const CARDS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+        let mut hands = std::collections::HashSet::<String>::new();
+        let mut input = String::new();
+        while hands.len() < size.min(13usize.pow(5)) {
+            let hand = if rng.chance(0.02) { "JJJJJ".to_string() } else { (0..5).map(|_| *rng.choose(&CARDS)).collect() };
+            if hands.insert(hand.clone()) {
+                input += &format!("{hand} {}\n", rng.between(1, 1000));
+            }
+        }
+        input
+    }
+}
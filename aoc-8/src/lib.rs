@@ -0,0 +1,175 @@
+//! Day 8: Haunted Wasteland. Follows left/right instructions through a network of nodes, alone and as a crowd of
+//! ghosts that all have to arrive at the same time.
+
+use std::collections::HashMap;
+
+use aoc_common::cycle::{self, Occurrences};
+use aoc_common::{math, parse, Generator, Graph, ParseError, Position, Rng, Solution, SolveError};
+use log::{debug, trace};
+use regex::Regex;
+
+pub struct Network {
+    /// The L and R instructions, repeated forever.
+    pub instructions: String,
+    /// Every node has an edge labelled L and one labelled R.
+    pub nodes: Graph<char>,
+}
+
+/// The node reached from `node` by following `direction`, L or R.
+pub fn follow(network: &Network, node: usize, direction: char) -> usize {
+    network.nodes.neighbours(node).iter().find(|(_, label)| *label == direction).unwrap().0
+}
+
+/// The nodes visited when following the instructions from `start`, forever.
+pub fn walk<'a>(network: &'a Network, start: usize) -> impl Iterator<Item = usize> + 'a {
+    network.instructions.chars().cycle().scan(start, |node, direction| {
+        *node = follow(network, *node, direction);
+        Some(*node)
+    })
+}
+
+/// The steps on which a ghost starting at `start` is on a node ending in Z. The ghost is in the same situation
+/// whenever it is on the same node at the same instruction, so its walk is found to repeat, keeping only two
+/// situations in memory. End nodes passed before the walk starts repeating are reached once, those on the repeating
+/// part again after every lap.
+pub fn ghost_cycles(network: &Network, start: usize) -> Occurrences {
+    let instructions = network.instructions.as_bytes();
+    let step = |&(node, i): &(usize, usize)| (follow(network, node, instructions[i] as char), (i + 1) % instructions.len());
+    let cycle = cycle::floyd((start, 0), step);
+    let situations = std::iter::successors(Some((start, 0)), |situation| Some(step(situation)));
+    Occurrences::new(cycle, situations.map(|(node, _)| network.nodes.name(node).ends_with('Z')))
+}
+
+/// Parses the instructions and one `AAA = (BBB, CCC)` line per node.
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let lines = aoc_common::numbered_lines(input);
+
+    let Some((instruction_line, instructions)) = lines.first() else {
+        return Err(ParseError::new(Position::start_of(1), "", "expected a line of instructions"));
+    };
+    if instructions.is_empty() {
+        return Err(ParseError::new(Position::start_of(*instruction_line), instructions, "expected L or R"));
+    }
+    if let Some(column) = instructions.find(|c| c != 'L' && c != 'R') {
+        let position = Position::start_of(*instruction_line).at_byte(instructions, column);
+        let instruction = instructions[column..].chars().next().unwrap();
+        return Err(ParseError::new(position, &instruction.to_string(), "expected L or R"));
+    }
+    let mut nodes = Graph::<char>::directed();
+
+    let line_regex = Regex::new(r"^(?<key>\w+) = \((?<left>\w+), (?<right>\w+)\)$").unwrap();
+    let mut first_mentions = HashMap::<String, Position>::new();
+    for (number, line) in &lines[1..] {
+        let result = parse::captures(&line_regex, line, Position::start_of(*number))?;
+        let key = result.name("key").unwrap();
+        if nodes.id(key.as_str()).is_some_and(|node| !nodes.neighbours(node).is_empty()) {
+            return Err(ParseError::new(Position::start_of(*number), key.as_str(), "the node is already defined"));
+        }
+        for (target, label) in [("left", 'L'), ("right", 'R')] {
+            let target = result.name(target).unwrap();
+            first_mentions.entry(target.as_str().to_string()).or_insert(Position::start_of(*number).at_byte(line, target.start()));
+            nodes.connect(key.as_str(), target.as_str(), label);
+        }
+    }
+    if let Some(node) = (0..nodes.len()).find(|&node| nodes.neighbours(node).is_empty()) {
+        let name = nodes.name(node);
+        return Err(ParseError::new(first_mentions[name], name, "the node leads nowhere, it is never defined"));
+    }
+    trace!("{}", nodes.to_dot());
+    Ok(Network { instructions: instructions.to_string(), nodes })
+}
+
+/// Part 2 tries every way of picking one end node per ghost, of which puzzle inputs have one.
+const MAX_COMBINATIONS: usize = 100_000;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Model = Network;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Network, ParseError> {
+        parse(input)
+    }
+
+    fn part1(network: &Network) -> Result<usize, SolveError> {
+        let node = |name| network.nodes.id(name).ok_or_else(|| SolveError::new(format!("there is no node {name}")));
+        let (start, end) = (node("AAA")?, node("ZZZ")?);
+        // every node is reached at every instruction within this many steps, if ever
+        let steps = network.nodes.len() * network.instructions.len();
+        let position = walk(network, start).take(steps).position(|node| node == end);
+        Ok(position.ok_or_else(|| SolveError::new("ZZZ is never reached from AAA"))? + 1)
+    }
+
+    /// Every ghost moves in a cycle, so all of them are on an end node together either before the last of them
+    /// starts going round, or at the first time that solves the congruences of all their cycles.
+    fn part2(network: &Network) -> Result<usize, SolveError> {
+        let ghosts = (0..network.nodes.len())
+            .filter(|&node| network.nodes.name(node).ends_with('A'))
+            .map(|node| ghost_cycles(network, node))
+            .collect::<Vec<Occurrences>>();
+        if ghosts.is_empty() {
+            return Err(SolveError::new("there are no start nodes"));
+        }
+        if let Some(steps) = cycle::first_common_in_tails(&ghosts) {
+            debug!("{:?} -> {}", ghosts, steps);
+            return Ok(steps);
+        }
+        let cycles = ghosts.into_iter().map(|ghost| ghost.repeating).collect::<Vec<Vec<(i128, i128)>>>();
+        if math::combinations_of_any(&cycles).is_none_or(|combinations| combinations > MAX_COMBINATIONS) {
+            return Err(SolveError::new("the ghosts reach too many end nodes to try every combination"));
+        }
+        let steps = math::first_common_time_of_any(&cycles).ok_or_else(|| SolveError::new("the ghosts never meet on end nodes"))?;
+        debug!("{:?} -> {}", cycles, steps);
+        usize::try_from(steps).map_err(|_| SolveError::new(format!("the ghosts only meet after {steps} steps")))
+    }
+}
+
+/// `size` instructions, at most 500 to leave enough node names, and two to four ghosts, the first of them going from
+/// AAA to ZZZ. Like in the puzzle, each ghost walks a loop whose length is a multiple of the instructions, and its
+/// end node leads where its start node does, so it is back on the end node after every lap.
+impl Generator for Day8 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let length = size.clamp(1, 500);
+        let instructions = (0..length).map(|_| if rng.chance(0.5) { 'L' } else { 'R' }).collect::<Vec<_>>();
+        let mut laps = [1, 2, 3, 5];
+        rng.shuffle(&mut laps);
+
+        let mut names = std::collections::HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+        let mut name = |rng: &mut Rng, last: char| loop {
+            let name = format!("{}{last}", rng.word(2).to_uppercase());
+            if names.insert(name.clone()) {
+                break name;
+            }
+        };
+        let mut paths = Vec::<Vec<String>>::new();
+        for (ghost, &lap) in laps[..rng.below(3) + 2].iter().enumerate() {
+            let mut path = vec![if ghost == 0 { "AAA".to_string() } else { name(rng, 'A') }];
+            for _ in 1..length * lap {
+                let last = (b'B' + rng.below(24) as u8) as char;
+                path.push(name(rng, last));
+            }
+            path.push(if ghost == 0 { "ZZZ".to_string() } else { name(rng, 'Z') });
+            paths.push(path);
+        }
+
+        // the direction a node is left by on the walk goes on along the path, the other one anywhere
+        let all = paths.concat();
+        let mut lines = Vec::<String>::new();
+        for path in &paths {
+            let start_other = rng.choose(&all).clone();
+            for (step, node) in path.iter().enumerate() {
+                let (next, other) = match step {
+                    0 => (&path[1], start_other.clone()),
+                    _ if step == path.len() - 1 => (&path[1], start_other.clone()),
+                    _ => (&path[step + 1], rng.choose(&all).clone()),
+                };
+                let (left, right) = if instructions[step % length] == 'L' { (next, &other) } else { (&other, next) };
+                lines.push(format!("{node} = ({left}, {right})"));
+            }
+        }
+        rng.shuffle(&mut lines);
+        format!("{}\n\n{}\n", instructions.iter().collect::<String>(), lines.join("\n"))
+    }
+}
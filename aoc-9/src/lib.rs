@@ -0,0 +1,118 @@
+//! Day 9: Mirage Maintenance. Extrapolates histories of values forwards and backwards by their differences.
+
+use aoc_common::{Generator, ParseError, Position, Rng, Solution, SolveError};
+use log::trace;
+
+/// The history followed by its differences, the differences of those, and so on until they are all zero. `None` if
+/// they never are before running out, or a difference does not fit.
+pub fn compute_derivatives(history: Vec<isize>) -> Option<Vec<Vec<isize>>> {
+    let mut result: Vec<Vec<isize>> = Vec::new();
+    result.push(history);
+
+    while result[result.len()-1].iter().any(|x| *x != 0) {
+        let previous = &result[result.len()-1];
+        if previous.len() == 1 {
+            return None;
+        }
+        let mut next = Vec::<isize>::new();
+        for i in 0..(previous.len()-1) {
+            next.push(previous[i+1].checked_sub(previous[i])?);
+        }
+        result.push(next);
+    }
+    Some(result)
+}
+
+/// Parses one history per line, each into its table of differences.
+pub fn parse(input: &str) -> Result<Vec<Vec<Vec<isize>>>, ParseError> {
+    let mut histories = Vec::<Vec<Vec<isize>>>::new();
+    for (number, line) in aoc_common::numbered_lines(input) {
+        let history = aoc_common::signed_numbers::<isize>(line, Position::start_of(number))?;
+        if history.is_empty() {
+            return Err(ParseError::new(Position::start_of(number), line, "expected a history of values"));
+        }
+        let Some(derivatives) = compute_derivatives(history) else {
+            return Err(ParseError::new(Position::start_of(number), line, "the differences never become all zero"));
+        };
+        histories.push(derivatives);
+    }
+    Ok(histories)
+}
+
+/// The value that follows the history, from its table of differences, if it fits.
+pub fn next_value(derivatives: &[Vec<isize>]) -> Option<isize> {
+    let mut forward_derivative: isize = 0;
+    for i in (0..derivatives.len()).rev() {
+        forward_derivative = forward_derivative.checked_add(derivatives[i][derivatives[i].len() - 1])?;
+    }
+    Some(forward_derivative)
+}
+
+/// The value that precedes the history, from its table of differences, if it fits.
+pub fn previous_value(derivatives: &[Vec<isize>]) -> Option<isize> {
+    let mut backward_derivative: isize = 0;
+    for i in (0..derivatives.len()).rev() {
+        backward_derivative = derivatives[i][0].checked_sub(backward_derivative)?;
+    }
+    Some(backward_derivative)
+}
+
+fn overflow() -> SolveError {
+    SolveError::new("the extrapolated values do not fit")
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Model = Vec<Vec<Vec<isize>>>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Vec<isize>>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(histories: &Vec<Vec<Vec<isize>>>) -> Result<isize, SolveError> {
+        let mut sum_forward: isize = 0;
+        for value in histories {
+            let forward_derivative = next_value(value).ok_or_else(overflow)?;
+            sum_forward = sum_forward.checked_add(forward_derivative).ok_or_else(overflow)?;
+            trace!("next value: {}", forward_derivative);
+        }
+        Ok(sum_forward)
+    }
+
+    fn part2(histories: &Vec<Vec<Vec<isize>>>) -> Result<isize, SolveError> {
+        let mut sum_backwards: isize = 0;
+        for value in histories {
+            let backward_derivative = previous_value(value).ok_or_else(overflow)?;
+            sum_backwards = sum_backwards.checked_add(backward_derivative).ok_or_else(overflow)?;
+            trace!("before first: {}", backward_derivative);
+        }
+        Ok(sum_backwards)
+    }
+}
+
+/// `size` histories of 5 to 21 values, each the values of a polynomial of low degree, so it is fully described by
+/// fewer differences than it has values.
+impl Generator for Day9 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let length = rng.between(5, 21);
+            let coefficients = (0..=rng.between(0, (length - 2).min(6))).map(|_| rng.between(-10, 10)).collect::<Vec<_>>();
+            let values = (0..length).map(|x| {
+                // in terms of binomials, a polynomial with integer coefficients has integer values
+                let mut binomial = 1;
+                coefficients.iter().enumerate().map(|(k, coefficient)| {
+                    let term = coefficient * binomial;
+                    binomial = binomial * (x - k as i64) / (k as i64 + 1);
+                    term
+                }).sum::<i64>().to_string()
+            });
+            input += &values.collect::<Vec<_>>().join(" ");
+            input.push('\n');
+        }
+        input
+    }
+}
@@ -0,0 +1,179 @@
+use crate::interval::Interval;
+
+/// An axis-aligned box in `N` dimensions: the points whose every coordinate lies in the interval of its dimension.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HyperRect<const N: usize> {
+    pub ranges: [Interval; N],
+}
+
+impl<const N: usize> HyperRect<N> {
+    pub fn new(ranges: [Interval; N]) -> Self {
+        HyperRect { ranges }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.iter().any(Interval::is_empty)
+    }
+
+    /// The number of integer points inside.
+    pub fn volume(&self) -> i128 {
+        self.ranges.iter().map(|range| range.len() as i128).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.ranges.iter().zip(point).all(|(range, value)| range.contains(value))
+    }
+
+    pub fn intersection(&self, other: &HyperRect<N>) -> Option<HyperRect<N>> {
+        let mut ranges = self.ranges;
+        for (range, other) in ranges.iter_mut().zip(&other.ranges) {
+            *range = range.intersection(other)?;
+        }
+        Some(HyperRect { ranges })
+    }
+
+    /// The parts whose coordinate in `dimension` is below `value` and from `value` on, either of which can be empty.
+    pub fn split_at(&self, dimension: usize, value: i64) -> (Option<HyperRect<N>>, Option<HyperRect<N>>) {
+        let (below, above) = self.ranges[dimension].split_at(value);
+        let with = |range: Interval| {
+            let mut ranges = self.ranges;
+            ranges[dimension] = range;
+            HyperRect { ranges }
+        };
+        (below.map(with), above.map(with))
+    }
+
+    /// Cuts `other` out of this box, leaving at most two disjoint boxes per dimension.
+    pub fn difference(&self, other: &HyperRect<N>) -> Vec<HyperRect<N>> {
+        if self.intersection(other).is_none() {
+            return if self.is_empty() { Vec::new() } else { vec![*self] };
+        }
+        let mut pieces = Vec::<HyperRect<N>>::new();
+        let mut rest = *self;
+        for dimension in 0..N {
+            let (below, inside) = rest.split_at(dimension, other.ranges[dimension].start);
+            pieces.extend(below);
+            let (inside, above) = inside.unwrap().split_at(dimension, other.ranges[dimension].end);
+            pieces.extend(above);
+            rest = inside.unwrap();
+        }
+        pieces
+    }
+}
+
+/// A set of integer points in `N` dimensions, kept as boxes that do not overlap so its volume is exact.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BoxSet<const N: usize> {
+    boxes: Vec<HyperRect<N>>,
+}
+
+impl<const N: usize> BoxSet<N> {
+    pub fn new() -> Self {
+        BoxSet { boxes: Vec::new() }
+    }
+
+    /// The disjoint boxes that make up the set, in no particular order.
+    pub fn boxes(&self) -> &[HyperRect<N>] {
+        &self.boxes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// The number of points in the set.
+    pub fn volume(&self) -> i128 {
+        self.boxes.iter().map(HyperRect::volume).sum()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.boxes.iter().any(|rect| rect.contains(point))
+    }
+
+    /// Adds the points of `rect` that are not in the set yet.
+    pub fn insert(&mut self, rect: HyperRect<N>) {
+        let mut pieces = if rect.is_empty() { Vec::new() } else { vec![rect] };
+        for existing in &self.boxes {
+            pieces = pieces.iter().flat_map(|piece| piece.difference(existing)).collect();
+        }
+        self.boxes.extend(pieces);
+    }
+
+    pub fn remove(&mut self, rect: &HyperRect<N>) {
+        self.boxes = self.boxes.iter().flat_map(|existing| existing.difference(rect)).collect();
+    }
+
+    pub fn union(&self, other: &BoxSet<N>) -> BoxSet<N> {
+        let mut result = self.clone();
+        for rect in &other.boxes {
+            result.insert(*rect);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &BoxSet<N>) -> BoxSet<N> {
+        let boxes = self.boxes.iter().flat_map(|a| other.boxes.iter().filter_map(|b| a.intersection(b))).collect();
+        BoxSet { boxes }
+    }
+
+    /// The points of this set that are not in `other`.
+    pub fn difference(&self, other: &BoxSet<N>) -> BoxSet<N> {
+        let mut result = self.clone();
+        for rect in &other.boxes {
+            result.remove(rect);
+        }
+        result
+    }
+}
+
+impl<const N: usize> FromIterator<HyperRect<N>> for BoxSet<N> {
+    fn from_iter<I: IntoIterator<Item = HyperRect<N>>>(rects: I) -> Self {
+        let mut set = BoxSet::new();
+        for rect in rects {
+            set.insert(rect);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(ranges: [(i64, i64); 2]) -> HyperRect<2> {
+        HyperRect::new(ranges.map(|(start, end)| Interval::new(start, end)))
+    }
+
+    /// The points of the set in a 20 by 20 square, to compare with the set operations on plain points.
+    fn points(set: &BoxSet<2>) -> Vec<[i64; 2]> {
+        (0..20).flat_map(|x| (0..20).map(move |y| [x, y])).filter(|&point| set.contains(point)).collect()
+    }
+
+    #[test]
+    fn overlapping_boxes_are_counted_once() {
+        let set = [rect([(0, 10), (0, 10)]), rect([(5, 15), (5, 15)]), rect([(2, 3), (2, 3)])].into_iter().collect::<BoxSet<2>>();
+        assert_eq!(set.volume(), 100 + 100 - 25);
+        assert_eq!(set.volume(), points(&set).len() as i128);
+        assert_eq!(rect([(0, 10), (3, 3)]).volume(), 0);
+        assert!(BoxSet::from_iter([rect([(0, 10), (3, 3)])]).is_empty());
+        let big = HyperRect::new([Interval::new(0, 1 << 40); 3]);
+        assert_eq!(big.volume(), 1 << 120);
+    }
+
+    #[test]
+    fn difference_and_intersection_match_the_points() {
+        let a = BoxSet::from_iter([rect([(0, 10), (0, 10)]), rect([(12, 18), (2, 6)])]);
+        let b = BoxSet::from_iter([rect([(4, 14), (4, 8)]), rect([(0, 2), (0, 2)])]);
+        let (a_points, b_points) = (points(&a), points(&b));
+        let difference = a_points.iter().copied().filter(|point| !b_points.contains(point)).collect::<Vec<_>>();
+        let intersection = a_points.iter().copied().filter(|point| b_points.contains(point)).collect::<Vec<_>>();
+        assert_eq!(points(&a.difference(&b)), difference);
+        assert_eq!(points(&a.intersection(&b)), intersection);
+        assert_eq!(a.difference(&b).volume(), difference.len() as i128);
+        assert_eq!(a.intersection(&b).volume(), intersection.len() as i128);
+        assert_eq!(a.union(&b).volume(), a.volume() + b.volume() - a.intersection(&b).volume());
+        // a box's difference is at most two pieces per dimension, none of them overlapping
+        let pieces = rect([(0, 10), (0, 10)]).difference(&rect([(3, 6), (4, 5)]));
+        assert_eq!((pieces.len(), pieces.iter().map(HyperRect::volume).sum::<i128>()), (4, 97));
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of the sequence `start, step(start), step(step(start)), ...` of a deterministic state machine: after
+/// `tail` states that never come back, the sequence repeats every `period` states.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub tail: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first iteration whose state equals the state at iteration `n`, always below `tail + period`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.tail {
+            n
+        } else {
+            self.tail + (n - self.tail) % self.period
+        }
+    }
+
+    /// The state at iteration `n`, recomputed from `start` in at most `tail + period` steps.
+    pub fn state_at<S>(&self, start: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        (0..self.reduce(n)).fold(start, |state, _| step(&state))
+    }
+}
+
+/// Brent's algorithm: finds the cycle with a constant number of states in memory, by comparing against a saved
+/// state whose distance doubles until the period fits.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut saved = start.clone();
+    let mut current = step(&start);
+    while saved != current {
+        if power == period {
+            saved = current.clone();
+            power *= 2;
+            period = 0;
+        }
+        current = step(&current);
+        period += 1;
+    }
+    let mut slow = start.clone();
+    let mut fast = (0..period).fold(start, |state, _| step(&state));
+    let mut tail = 0;
+    while slow != fast {
+        slow = step(&slow);
+        fast = step(&fast);
+        tail += 1;
+    }
+    Cycle { tail, period }
+}
+
+/// Floyd's tortoise and hare: finds the cycle with two states in memory, stepping the hare twice as fast.
+pub fn floyd<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut slow = step(&start);
+    let mut fast = step(&slow);
+    while slow != fast {
+        slow = step(&slow);
+        let next = step(&fast);
+        fast = step(&next);
+    }
+    let mut tail = 0;
+    slow = start;
+    while slow != fast {
+        slow = step(&slow);
+        fast = step(&fast);
+        tail += 1;
+    }
+    let mut period = 1;
+    fast = step(&slow);
+    while slow != fast {
+        fast = step(&fast);
+        period += 1;
+    }
+    Cycle { tail, period }
+}
+
+/// The iterations of a sequence on which something happens, split by its cycle: those in the tail happen once and
+/// never again, those in the cycle repeat every period.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Occurrences {
+    pub cycle: Cycle,
+    /// The iterations before `tail` it happens on, in order.
+    pub once: Vec<usize>,
+    /// `(offset, period)` for every iteration in the cycle it happens on, in order, as `math::first_common_time` takes
+    /// them.
+    pub repeating: Vec<(i128, i128)>,
+}
+
+impl Occurrences {
+    /// Splits the iterations on which `happens` is true, given for at least the first `tail + period` iterations.
+    pub fn new(cycle: Cycle, happens: impl IntoIterator<Item = bool>) -> Self {
+        let iterations = happens.into_iter().take(cycle.tail + cycle.period).enumerate().filter(|&(_, happens)| happens).map(|(n, _)| n);
+        let (once, repeating): (Vec<usize>, Vec<usize>) = iterations.partition(|&n| n < cycle.tail);
+        let repeating = repeating.into_iter().map(|n| (n as i128, cycle.period as i128)).collect();
+        Occurrences { cycle, once, repeating }
+    }
+
+    /// Whether it happens on iteration `n`.
+    pub fn contains(&self, n: usize) -> bool {
+        if n < self.cycle.tail {
+            self.once.binary_search(&n).is_ok()
+        } else {
+            self.repeating.binary_search(&(self.cycle.reduce(n) as i128, self.cycle.period as i128)).is_ok()
+        }
+    }
+}
+
+/// The first iteration before the end of the longest tail on which everything happens together. Later iterations
+/// are all in the cycles, where `math::first_common_time_of_any` finds them from the repeating occurrences.
+pub fn first_common_in_tails(occurrences: &[Occurrences]) -> Option<usize> {
+    let tails = occurrences.iter().map(|occurrences| occurrences.cycle.tail).max().unwrap_or(0);
+    (0..tails).find(|&n| occurrences.iter().all(|occurrences| occurrences.contains(n)))
+}
+
+/// Every state of a sequence up to the point where it repeats, which makes any iteration a lookup.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// The states of iterations `0..tail + period`.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+/// Finds the cycle by remembering every state, so it only steps until the first repeat. States are told apart by
+/// comparing them in full, the hash only picks where to look.
+pub fn find_cycle<S: Clone + Eq + Hash>(start: S, step: impl FnMut(&S) -> S) -> History<S> {
+    find_cycle_within(start, usize::MAX, step).unwrap()
+}
+
+/// Like `find_cycle`, but gives up with `None` once `max_states` states went by without a repeat.
+pub fn find_cycle_within<S: Clone + Eq + Hash>(start: S, max_states: usize, mut step: impl FnMut(&S) -> S) -> Option<History<S>> {
+    let mut seen = HashMap::<S, usize>::new();
+    let mut states = Vec::<S>::new();
+    let mut current = start;
+    loop {
+        if let Some(&tail) = seen.get(&current) {
+            let period = states.len() - tail;
+            return Some(History { cycle: Cycle { tail, period }, states });
+        }
+        if states.len() == max_states {
+            return None;
+        }
+        seen.insert(current.clone(), states.len());
+        let next = step(&current);
+        states.push(current);
+        current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5, 6 over and over.
+    fn step(&n: &usize) -> usize {
+        if n < 6 { n + 1 } else { 3 }
+    }
+
+    /// The cycle as found by each of the finders.
+    fn found(start: usize, step: fn(&usize) -> usize) -> [Cycle; 3] {
+        [brent(start, step), floyd(start, step), find_cycle(start, step).cycle]
+    }
+
+    #[test]
+    fn every_finder_finds_the_tail_and_period() {
+        assert_eq!(found(0, step), [Cycle { tail: 3, period: 4 }; 3]);
+        assert_eq!(found(5, step), [Cycle { tail: 0, period: 4 }; 3]);
+        assert_eq!(found(7, |&n| n), [Cycle { tail: 0, period: 1 }; 3]);
+        assert_eq!(found(0, |&n| (n + 1).min(100)), [Cycle { tail: 100, period: 1 }; 3]);
+    }
+
+    #[test]
+    fn iterations_are_reduced_into_the_first_lap() {
+        let cycle = Cycle { tail: 3, period: 4 };
+        assert_eq!((0..12).map(|n| cycle.reduce(n)).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, 3]);
+        assert_eq!(cycle.reduce(1_000_000_000), 3 + (1_000_000_000 - 3) % 4);
+        for cycle in found(0, step) {
+            assert_eq!((0..50).map(|n| cycle.state_at(0, step, n)).collect::<Vec<_>>(), (0..50).map(|n| (0..n).fold(0, |state, _| step(&state))).collect::<Vec<_>>());
+        }
+        let history = find_cycle(0, step);
+        assert_eq!(history.states, [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(*history.state_at(1_000_000_000), cycle.state_at(0, step, 1_000_000_000));
+        assert!(find_cycle_within(0, 6, step).is_none());
+        assert_eq!(find_cycle_within(0, 7, step).unwrap().cycle, cycle);
+    }
+
+    #[test]
+    fn occurrences_in_the_tail_happen_once() {
+        let cycle = Cycle { tail: 3, period: 4 };
+        let states = std::iter::successors(Some(0), |n| Some(step(n)));
+        let odd = Occurrences::new(cycle, states.map(|n| n % 2 == 1));
+        assert_eq!((odd.once.as_slice(), odd.repeating.as_slice()), (&[1][..], &[(3, 4), (5, 4)][..]));
+        assert_eq!((0..12).filter(|&n| odd.contains(n)).collect::<Vec<_>>(), [1, 3, 5, 7, 9, 11]);
+
+        let first = Occurrences::new(Cycle { tail: 2, period: 1 }, [false, true, false]);
+        let second = Occurrences::new(Cycle { tail: 1, period: 1 }, [false, true]);
+        assert_eq!(first_common_in_tails(&[first.clone(), second.clone()]), Some(1));
+        assert_eq!(first_common_in_tails(&[first, Occurrences::new(Cycle { tail: 1, period: 2 }, [false, false, true])]), None);
+        assert_eq!(first_common_in_tails(&[second]), None);
+    }
+}
@@ -0,0 +1,243 @@
+/// Integer types positions can be made of. Stepping off the edge of the type gives `None` instead of wrapping.
+pub trait Coordinate: Copy {
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! coordinate {
+    ($($type:ty),*) => {
+        $(
+            impl Coordinate for $type {
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$type>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Whether a direction runs along a row or a column.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    pub fn perpendicular(self) -> Axis {
+        match self {
+            Axis::Horizontal => Axis::Vertical,
+            Axis::Vertical => Axis::Horizontal,
+        }
+    }
+}
+
+/// One of the four directions on a grid. North is up, towards smaller `y`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Reads `U/R/D/L`, `N/E/S/W` and arrows, both `^>v<` and `↑→↓←`.
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' | '^' | '↑' => Some(Direction::North),
+            'R' | 'E' | '>' | '→' => Some(Direction::East),
+            'D' | 'S' | 'v' | '↓' => Some(Direction::South),
+            'L' | 'W' | '<' | '←' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The direction as an arrow, `↑→↓←`.
+    pub fn arrow(self) -> char {
+        ['↑', '→', '↓', '←'][self.index()]
+    }
+
+    /// The position of the direction in `ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn axis(self) -> Axis {
+        match self {
+            Direction::North | Direction::South => Axis::Vertical,
+            Direction::East | Direction::West => Axis::Horizontal,
+        }
+    }
+
+    /// Moves `distance` steps from `(x, y)`, or `None` if that leaves the coordinate type.
+    pub fn checked_offset_by<T: Coordinate>(self, (x, y): (T, T), distance: T) -> Option<(T, T)> {
+        match self {
+            Direction::North => Some((x, y.checked_sub(distance)?)),
+            Direction::East => Some((x.checked_add(distance)?, y)),
+            Direction::South => Some((x, y.checked_add(distance)?)),
+            Direction::West => Some((x.checked_sub(distance)?, y)),
+        }
+    }
+
+    pub fn checked_offset<T: Coordinate>(self, position: (T, T)) -> Option<(T, T)> {
+        self.checked_offset_by(position, T::ONE)
+    }
+
+    /// Moves `distance` steps from `position`, panicking if that leaves the coordinate type.
+    pub fn offset_by<T: Coordinate>(self, position: (T, T), distance: T) -> (T, T) {
+        self.checked_offset_by(position, distance).unwrap_or_else(|| panic!("moving {self:?} overflows"))
+    }
+
+    pub fn offset<T: Coordinate>(self, position: (T, T)) -> (T, T) {
+        self.offset_by(position, T::ONE)
+    }
+}
+
+/// One of the eight directions on a grid, including the diagonals.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// The position of the direction in `ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 4) % 8]
+    }
+
+    /// The axis of the straight directions, diagonals have none.
+    pub fn axis(self) -> Option<Axis> {
+        match self.components() {
+            [direction] => Some(direction.axis()),
+            _ => None,
+        }
+    }
+
+    /// The straight directions this one is made of, one step in each.
+    pub fn components(self) -> &'static [Direction] {
+        match self {
+            Direction8::North => &[Direction::North],
+            Direction8::NorthEast => &[Direction::North, Direction::East],
+            Direction8::East => &[Direction::East],
+            Direction8::SouthEast => &[Direction::South, Direction::East],
+            Direction8::South => &[Direction::South],
+            Direction8::SouthWest => &[Direction::South, Direction::West],
+            Direction8::West => &[Direction::West],
+            Direction8::NorthWest => &[Direction::North, Direction::West],
+        }
+    }
+
+    pub fn checked_offset<T: Coordinate>(self, position: (T, T)) -> Option<(T, T)> {
+        self.components().iter().try_fold(position, |position, direction| direction.checked_offset(position))
+    }
+
+    pub fn offset<T: Coordinate>(self, position: (T, T)) -> (T, T) {
+        self.checked_offset(position).unwrap_or_else(|| panic!("moving {self:?} overflows"))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        Direction8::ALL[direction.index() * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_go_round_and_opposites_go_back() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_ne!(direction.axis(), direction.turn_right().axis());
+            assert_eq!(direction.opposite().offset(direction.offset((5, 5))), (5, 5));
+        }
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::NorthEast.opposite(), Direction8::SouthWest);
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+        assert_eq!(Direction8::SouthEast.offset((0, 0)), (1, 1));
+        assert_eq!(Direction8::SouthEast.axis(), None);
+    }
+
+    #[test]
+    fn every_spelling_of_a_direction_is_read() {
+        for (direction, spellings) in Direction::ALL.into_iter().zip(["UN^↑", "RE>→", "DSv↓", "LW<←"]) {
+            assert!(spellings.chars().all(|c| Direction::from_char(c) == Some(direction)), "{spellings}");
+            assert_eq!(Direction::from_char(direction.arrow()), Some(direction));
+        }
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::from_char('u'), None);
+    }
+
+    #[test]
+    fn leaving_the_coordinate_type_is_none() {
+        assert_eq!(Direction::North.checked_offset((0_usize, 0_usize)), None);
+        assert_eq!(Direction::East.checked_offset_by((250_u8, 0), 5), Some((255, 0)));
+        assert_eq!(Direction::East.checked_offset_by((250_u8, 0), 6), None);
+        assert_eq!(Direction8::NorthWest.checked_offset((0_usize, 3_usize)), None);
+    }
+}
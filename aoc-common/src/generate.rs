@@ -0,0 +1,157 @@
+use crate::grid::Grid;
+use std::collections::{HashMap, VecDeque};
+
+/// Writes random puzzle inputs in the exact format of a day, for stress tests and scaling runs. The same seed and
+/// size always give the same input, and a larger size a larger input; what the size counts is up to the day.
+pub trait Generator {
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// A small seedable random number generator (SplitMix64). It is not meant for anything but test inputs, its only
+/// promise is that a seed gives the same numbers on every platform and build.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "cannot pick a number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number from `low` to `high`, both included.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "cannot pick a number between {low} and {high}");
+        let span = (high as i128 - low as i128 + 1) as u128;
+        (low as i128 + ((self.next_u64() as u128 * span) >> 64) as i128) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// A word of `length` random lowercase letters.
+    pub fn word(&mut self, length: usize) -> String {
+        (0..length).map(|_| (b'a' + self.below(26) as u8) as char).collect()
+    }
+}
+
+/// A random simple closed loop on the points `(0..=width, 0..=height)`, as the points it passes in order, one step
+/// apart. It is the outline of a random blob of cells that has no holes and whose cells never touch only at a corner,
+/// which is what keeps the loop from touching itself.
+pub fn polygon(rng: &mut Rng, width: usize, height: usize) -> Vec<(usize, usize)> {
+    assert!(width > 0 && height > 0, "cannot draw a polygon in a {width}x{height} area");
+    let mut inside = Grid::filled(width, height, false);
+    let mut cells = vec![(width / 2, height / 2)];
+    inside[cells[0]] = true;
+    let target = (width * height / 2).max(1);
+    for _ in 0..target * 20 {
+        if cells.len() >= target {
+            break;
+        }
+        let cell = *rng.choose(&cells);
+        let neighbours = inside.neighbours4(cell).collect::<Vec<_>>();
+        let next = *rng.choose(&neighbours);
+        if !inside[next] {
+            inside[next] = true;
+            cells.push(next);
+        }
+    }
+    while fill_holes(&mut inside) | fill_pinches(&mut inside) {}
+
+    // every cell side between the blob and the outside, going clockwise around the blob
+    let mut next = HashMap::<(usize, usize), (usize, usize)>::new();
+    for ((x, y), &cell) in inside.iter() {
+        if !cell {
+            continue;
+        }
+        let outside = |dx: isize, dy: isize| !inside.get((x as isize + dx, y as isize + dy)).copied().unwrap_or(false);
+        if outside(0, -1) {
+            next.insert((x, y), (x + 1, y));
+        }
+        if outside(1, 0) {
+            next.insert((x + 1, y), (x + 1, y + 1));
+        }
+        if outside(0, 1) {
+            next.insert((x + 1, y + 1), (x, y + 1));
+        }
+        if outside(-1, 0) {
+            next.insert((x, y + 1), (x, y));
+        }
+    }
+    let start = *next.keys().min().unwrap();
+    let mut points = vec![start];
+    let mut current = next[&start];
+    while current != start {
+        points.push(current);
+        current = next[&current];
+    }
+    points
+}
+
+/// Adds the cells the outside cannot reach to the blob. Returns whether there were any.
+fn fill_holes(inside: &mut Grid<bool>) -> bool {
+    let mut outside = Grid::filled(inside.width(), inside.height(), false);
+    let mut queue = inside.positions()
+        .filter(|&(x, y)| (x == 0 || y == 0 || x == inside.width() - 1 || y == inside.height() - 1) && !inside[(x, y)])
+        .collect::<VecDeque<_>>();
+    for &position in &queue {
+        outside[position] = true;
+    }
+    while let Some(position) = queue.pop_front() {
+        for next in inside.neighbours4(position) {
+            if !inside[next] && !outside[next] {
+                outside[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    let holes = inside.positions().filter(|&position| !inside[position] && !outside[position]).collect::<Vec<_>>();
+    for &position in &holes {
+        inside[position] = true;
+    }
+    !holes.is_empty()
+}
+
+/// Adds a cell wherever two cells of the blob only touch at a corner. Returns whether there were any.
+fn fill_pinches(inside: &mut Grid<bool>) -> bool {
+    let mut changed = false;
+    for y in 1..inside.height() {
+        for x in 1..inside.width() {
+            let [a, b, c, d] = [(x - 1, y - 1), (x, y - 1), (x - 1, y), (x, y)];
+            if inside[a] && inside[d] && !inside[b] && !inside[c] {
+                inside[b] = true;
+                changed = true;
+            } else if inside[b] && inside[c] && !inside[a] && !inside[d] {
+                inside[a] = true;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
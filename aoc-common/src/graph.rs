@@ -0,0 +1,455 @@
+use crate::parse::{ParseError, Position};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// How an edge weight appears in DOT, as the label of the edge. Unweighted graphs use `()`, which has no label.
+pub trait Label: Sized {
+    fn label(&self) -> Option<String>;
+    fn from_label(label: Option<&str>) -> Option<Self>;
+}
+
+impl Label for () {
+    fn label(&self) -> Option<String> {
+        None
+    }
+
+    fn from_label(_label: Option<&str>) -> Option<Self> {
+        Some(())
+    }
+}
+
+macro_rules! impl_label {
+    ($($t:ty),*) => {
+        $(impl Label for $t {
+            fn label(&self) -> Option<String> {
+                Some(self.to_string())
+            }
+
+            fn from_label(label: Option<&str>) -> Option<Self> {
+                label?.parse().ok()
+            }
+        })*
+    };
+}
+
+impl_label!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char, String);
+
+/// A graph whose nodes are named by strings. Every name is interned once into a node id, the index of the node in
+/// the order the nodes were added, so the rest of the graph only deals in ids.
+#[derive(Clone, Debug)]
+pub struct Graph<W = ()> {
+    directed: bool,
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacency: Vec<Vec<(usize, W)>>,
+    edges: Vec<(usize, usize, W)>,
+}
+
+impl<W> Graph<W> {
+    pub fn undirected() -> Self {
+        Graph { directed: false, names: Vec::new(), ids: HashMap::new(), adjacency: Vec::new(), edges: Vec::new() }
+    }
+
+    pub fn directed() -> Self {
+        Graph { directed: true, ..Graph::undirected() }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The id of the node called `name`, adding the node if there is none yet.
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        self.ids.insert(name.to_string(), self.names.len());
+        self.names.push(name.to_string());
+        self.adjacency.push(Vec::new());
+        self.names.len() - 1
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// The names of all nodes, indexed by id.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The nodes an edge leads to from `id`, with the weight of that edge. Undirected edges are listed at both ends.
+    pub fn neighbours(&self, id: usize) -> &[(usize, W)] {
+        &self.adjacency[id]
+    }
+
+    /// Every edge once, in the order they were added.
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    /// The weight of the first edge from `from` to `to`, if there is one.
+    pub fn weight(&self, from: usize, to: usize) -> Option<&W> {
+        self.adjacency[from].iter().find(|(next, _)| *next == to).map(|(_, weight)| weight)
+    }
+
+    /// The ids of the nodes reachable from `start`, in the order a breadth first search reaches them.
+    pub fn breadth_first(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        visited[start] = true;
+        let mut order = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &(next, _) in &self.adjacency[node] {
+                if !visited[next] {
+                    visited[next] = true;
+                    order.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self.adjacency[from].push((to, weight.clone()));
+        if !self.directed && from != to {
+            self.adjacency[to].push((from, weight.clone()));
+        }
+        self.edges.push((from, to, weight));
+    }
+
+    /// Adds an edge between the nodes called `from` and `to`, adding the nodes as needed, and returns their ids.
+    pub fn connect(&mut self, from: &str, to: &str, weight: W) -> (usize, usize) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.add_edge(from, to, weight);
+        (from, to)
+    }
+
+    /// The same graph with every edge turned around, which is the graph itself if it is undirected. Node ids are kept.
+    pub fn reversed(&self) -> Graph<W> {
+        let mut reversed = Graph { directed: self.directed, names: self.names.clone(), ids: self.ids.clone(), ..Graph::undirected() };
+        reversed.adjacency = vec![Vec::new(); self.len()];
+        for (from, to, weight) in &self.edges {
+            reversed.add_edge(*to, *from, weight.clone());
+        }
+        reversed
+    }
+}
+
+impl<W: Label> Graph<W> {
+    /// The graph in Graphviz DOT, with weights as edge labels. Every node is listed, so isolated ones survive.
+    pub fn to_dot(&self) -> String {
+        let (keyword, operator) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        let mut dot = format!("{keyword} {{\n");
+        for name in &self.names {
+            writeln!(dot, "    {};", quote(name)).unwrap();
+        }
+        for (from, to, weight) in &self.edges {
+            write!(dot, "    {} {operator} {}", quote(&self.names[*from]), quote(&self.names[*to])).unwrap();
+            if let Some(label) = weight.label() {
+                write!(dot, " [label={}]", quote(&label)).unwrap();
+            }
+            dot.push_str(";\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<W: Label + Clone> Graph<W> {
+    /// Reads the part of DOT that describes nodes and edges: node and edge statements, including chains like
+    /// `a -> b -> c`. The `weight` or else the `label` attribute of an edge is its weight. Other attributes, default
+    /// attribute statements and subgraph braces are skipped.
+    pub fn from_dot(input: &str) -> Result<Graph<W>, ParseError> {
+        let tokens = tokenize(input)?;
+        let mut cursor = Cursor { tokens: &tokens, index: 0, end: end_position(input) };
+
+        let mut token = cursor.next("graph or digraph")?;
+        if token.is("strict") {
+            token = cursor.next("graph or digraph")?;
+        }
+        let mut graph = if token.is("digraph") {
+            Graph::directed()
+        } else if token.is("graph") {
+            Graph::undirected()
+        } else {
+            return Err(token.error("expected graph or digraph"));
+        };
+        let operator = if graph.directed { "->" } else { "--" };
+        if !cursor.peek_is("{") {
+            cursor.id()?;
+        }
+        cursor.expect("{")?;
+
+        let mut depth = 1;
+        while depth > 0 {
+            let token = cursor.next("}")?;
+            if token.is("{") {
+                depth += 1;
+            } else if token.is("}") {
+                depth -= 1;
+            } else if token.is(";") {
+                // statements may end in a semicolon
+            } else if token.is("graph") || token.is("node") || token.is("edge") {
+                cursor.attributes()?;
+            } else if token.is("subgraph") {
+                if !cursor.peek_is("{") {
+                    cursor.id()?;
+                }
+            } else if token.is_symbol() {
+                return Err(token.error("expected a statement"));
+            } else if cursor.peek_is("=") {
+                // a graph attribute
+                cursor.expect("=")?;
+                cursor.id()?;
+            } else {
+                let mut nodes = vec![graph.add_node(&token.text)];
+                while cursor.peek_is("--") || cursor.peek_is("->") {
+                    cursor.expect(operator)?;
+                    nodes.push(graph.add_node(&cursor.id()?.text));
+                }
+                let attributes = cursor.attributes()?;
+                if nodes.len() > 1 {
+                    let label = attributes.get("weight").or(attributes.get("label"));
+                    let weight = W::from_label(label.map(|token| token.text.as_str()))
+                        .ok_or_else(|| label.copied().unwrap_or(token).error(&format!("invalid {}", std::any::type_name::<W>())))?;
+                    for pair in nodes.windows(2) {
+                        graph.add_edge(pair[0], pair[1], weight.clone());
+                    }
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Writes `name` as a DOT id, quoting it unless it is a plain identifier or number.
+fn quote(name: &str) -> String {
+    let identifier = name.chars().all(|c| c.is_alphanumeric() || c == '_') && !name.starts_with(|c: char| c.is_ascii_digit());
+    let numeral = name.chars().all(|c| c.is_ascii_digit() || c == '.' || c == '-') && name.parse::<f64>().is_ok();
+    if !name.is_empty() && (identifier || numeral) {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    text: String,
+    quoted: bool,
+    position: Position,
+}
+
+impl Token {
+    fn is(&self, text: &str) -> bool {
+        !self.quoted && self.text == text
+    }
+
+    fn is_symbol(&self) -> bool {
+        !self.quoted && ["{", "}", "[", "]", "=", ";", ",", "--", "->"].contains(&self.text.as_str())
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.position, &self.text, message)
+    }
+}
+
+fn end_position(input: &str) -> Position {
+    let line = input.lines().count().max(1);
+    Position::start_of(line).at_byte(input.lines().last().unwrap_or(""), input.lines().last().unwrap_or("").len())
+}
+
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    index: usize,
+    end: Position,
+}
+
+impl<'a> Cursor<'a> {
+    fn next(&mut self, expected: &str) -> Result<&'a Token, ParseError> {
+        let token = self.tokens.get(self.index).ok_or_else(|| ParseError::new(self.end, "", format!("expected {expected}")))?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn peek_is(&self, symbol: &str) -> bool {
+        self.tokens.get(self.index).is_some_and(|token| token.is(symbol))
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<&'a Token, ParseError> {
+        let token = self.next(symbol)?;
+        if !token.is(symbol) {
+            return Err(token.error(&format!("expected {symbol}")));
+        }
+        Ok(token)
+    }
+
+    fn id(&mut self) -> Result<&'a Token, ParseError> {
+        let token = self.next("an id")?;
+        if token.is_symbol() {
+            return Err(token.error("expected an id"));
+        }
+        Ok(token)
+    }
+
+    /// The `key=value` pairs of the attribute lists that follow, if any.
+    fn attributes(&mut self) -> Result<HashMap<&'a str, &'a Token>, ParseError> {
+        let mut attributes = HashMap::new();
+        while self.peek_is("[") {
+            self.expect("[")?;
+            while !self.peek_is("]") {
+                let key = self.id()?;
+                if self.peek_is("=") {
+                    self.expect("=")?;
+                    attributes.insert(key.text.as_str(), self.id()?);
+                }
+                if self.peek_is(",") || self.peek_is(";") {
+                    self.index += 1;
+                }
+            }
+            self.expect("]")?;
+        }
+        Ok(attributes)
+    }
+}
+
+/// Splits DOT into ids and symbols, skipping whitespace and comments.
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::<Token>::new();
+    let mut in_comment = false;
+    for (index, line) in input.lines().enumerate() {
+        let start = Position::start_of(index + 1);
+        let mut rest = line;
+        let offset = |rest: &str| line.len() - rest.len();
+        if !in_comment && line.trim_start().starts_with('#') {
+            continue;
+        }
+        loop {
+            if in_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        rest = &rest[end + 2..];
+                        in_comment = false;
+                    }
+                    None => break,
+                }
+            }
+            rest = rest.trim_start();
+            let position = start.at_byte(line, offset(rest));
+            if rest.is_empty() || rest.starts_with("//") {
+                break;
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                rest = comment;
+                in_comment = true;
+            } else if let Some(quoted) = rest.strip_prefix('"') {
+                let mut text = String::new();
+                let mut chars = quoted.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        Some((i, '"')) => break i,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, '"')) => text.push('"'),
+                            Some((_, '\\')) => text.push('\\'),
+                            Some((_, c)) => {
+                                text.push('\\');
+                                text.push(c);
+                            }
+                            None => return Err(ParseError::new(position, rest, "unterminated string")),
+                        },
+                        Some((_, c)) => text.push(c),
+                        None => return Err(ParseError::new(position, rest, "unterminated string")),
+                    }
+                };
+                tokens.push(Token { text, quoted: true, position });
+                rest = &quoted[end + 1..];
+            } else if rest.starts_with("--") || rest.starts_with("->") {
+                tokens.push(Token { text: rest[..2].to_string(), quoted: false, position });
+                rest = &rest[2..];
+            } else if rest.starts_with(['{', '}', '[', ']', '=', ';', ',']) {
+                tokens.push(Token { text: rest[..1].to_string(), quoted: false, position });
+                rest = &rest[1..];
+            } else {
+                // ids are letters, digits and underscores, or numerals which may be negative
+                let numeral = rest.strip_prefix('-').unwrap_or(rest).starts_with(|c: char| c.is_ascii_digit() || c == '.');
+                let sign = if numeral && rest.starts_with('-') { 1 } else { 0 };
+                let end = rest[sign..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || numeral && c == '.'))
+                    .map_or(rest.len(), |end| end + sign);
+                if end == 0 {
+                    return Err(ParseError::new(position, &rest[..rest.chars().next().unwrap().len_utf8()], "unexpected character"));
+                }
+                tokens.push(Token { text: rest[..end].to_string(), quoted: false, position });
+                rest = &rest[end..];
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The nodes, the edges by name and whether they are directed, which is what DOT records of a graph.
+    fn shape<W: Clone>(graph: &Graph<W>) -> (bool, Vec<String>, Vec<(String, String, W)>) {
+        let edges = graph.edges().iter().map(|(from, to, weight)| (graph.name(*from).to_string(), graph.name(*to).to_string(), weight.clone())).collect();
+        (graph.is_directed(), graph.names().to_vec(), edges)
+    }
+
+    #[test]
+    fn graphs_read_back_what_they_write() {
+        let mut directed = Graph::<usize>::directed();
+        directed.connect("a", "b", 3);
+        directed.connect("b", "a", 4);
+        directed.connect("button press", "say \"hi\"", 0);
+        directed.connect("2", "a\\b", 12);
+        directed.add_node("alone");
+        let read = Graph::<usize>::from_dot(&directed.to_dot()).unwrap();
+        assert_eq!(shape(&read), shape(&directed));
+        assert_eq!(read.weight(read.id("b").unwrap(), read.id("a").unwrap()), Some(&4));
+
+        let mut undirected = Graph::<()>::undirected();
+        undirected.connect("jqt", "rhn", ());
+        undirected.connect("rhn", "xhk", ());
+        let read = Graph::<()>::from_dot(&undirected.to_dot()).unwrap();
+        assert_eq!(shape(&read), shape(&undirected));
+        assert_eq!(read.neighbours(read.id("rhn").unwrap()).len(), 2);
+    }
+
+    #[test]
+    fn dot_written_by_hand_is_read() {
+        let dot = "strict digraph G {\n  node [shape=box];\n  rankdir = LR;\n  a -> b -> c [weight=2, color=red];\n  subgraph x { c -> \"d e\" [label=5] }\n}\n";
+        let graph = Graph::<u32>::from_dot(dot).unwrap();
+        assert_eq!(graph.names(), ["a", "b", "c", "d e"]);
+        assert_eq!(graph.edges(), [(0, 1, 2), (1, 2, 2), (2, 3, 5)]);
+        assert!(Graph::<u32>::from_dot("digraph { a -> b [label=x] }").is_err());
+        assert!(Graph::<()>::from_dot("graph { a -> b }").is_err());
+    }
+
+    #[test]
+    fn reversing_and_searching_follow_the_edges() {
+        let mut graph = Graph::<()>::directed();
+        for (from, to) in [("a", "b"), ("b", "c"), ("d", "c")] {
+            graph.connect(from, to, ());
+        }
+        assert_eq!(graph.breadth_first(0), [0, 1, 2]);
+        assert_eq!(graph.reversed().breadth_first(graph.id("c").unwrap()), [2, 1, 3, 0]);
+    }
+}
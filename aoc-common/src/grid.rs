@@ -0,0 +1,248 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::direction::{Direction, Direction8};
+use crate::parse::{numbered_lines, ParseError, Position};
+
+/// A rectangular map of cells, stored row by row. Positions are `(x, y)` with `x` the column and `y` the row,
+/// counted from the top left corner.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics unless there are exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "a {width}x{height} grid needs {} cells", width * height);
+        Grid { width, height, cells }
+    }
+
+    /// Maps every character of every non-empty line, rejecting characters the mapper does not know, rows that are not
+    /// as wide as the first one, and input without any rows.
+    pub fn from_str(input: &str, mapper: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        Self::from_lines(&numbered_lines(input), mapper)
+    }
+
+    /// Like `from_str`, for lines that were already split off the input together with their line numbers.
+    pub fn from_lines(lines: &[(usize, &str)], mapper: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        let Some((_, first)) = lines.first() else {
+            return Err(ParseError::new(Position::start_of(1), "", "expected a grid"));
+        };
+        let width = first.chars().count();
+        let mut cells = Vec::<T>::with_capacity(width * lines.len());
+        for (number, line) in lines {
+            if line.chars().count() != width {
+                return Err(ParseError::new(Position::start_of(*number), line, format!("expected {width} columns")));
+            }
+            for (x, c) in line.chars().enumerate() {
+                let cell = mapper(c)
+                    .ok_or_else(|| ParseError::new(Position::new(*number, x + 1), &c.to_string(), "unexpected character"))?;
+                cells.push(cell);
+            }
+        }
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Converts a signed position to an index into the grid, if it lies inside.
+    pub fn checked(&self, (x, y): (isize, isize)) -> Option<(usize, usize)> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+
+    pub fn contains(&self, position: (isize, isize)) -> bool {
+        self.checked(position).is_some()
+    }
+
+    pub fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.checked(position).map(|position| &self[position])
+    }
+
+    pub fn get_mut(&mut self, position: (isize, isize)) -> Option<&mut T> {
+        self.checked(position).map(|position| &mut self[position])
+    }
+
+    /// The cell at `position` on an infinite plane tiled with copies of the grid, none if the grid has no cells to
+    /// tile it with.
+    pub fn wrapping(&self, (x, y): (isize, isize)) -> Option<&T> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        Some(&self[(x.rem_euclid(self.width as isize) as usize, y.rem_euclid(self.height as isize) as usize)])
+    }
+
+    /// The position next to `position` in `direction`, if it lies inside the grid.
+    pub fn step(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.step8(position, direction.into())
+    }
+
+    pub fn step8(&self, position: (usize, usize), direction: Direction8) -> Option<(usize, usize)> {
+        direction.checked_offset(position).filter(|(x, y)| *x < self.width && *y < self.height)
+    }
+
+    /// The orthogonal neighbours of `position` that lie inside the grid, clockwise starting north.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The orthogonal and diagonal neighbours of `position` that lie inside the grid, clockwise starting north.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction8::ALL.into_iter().filter_map(move |direction| self.step8(position, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a width of zero, which an empty input would produce.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of a grid {} wide", self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first position, row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells.iter().position(predicate).map(|i| (i % self.width, i / self.width))
+    }
+
+    pub fn map<U>(&self, mapper: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(mapper).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.width).flat_map(|x| self.column(x).cloned()).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter clockwise, the first column becomes the first row.
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.width).flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone())).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter counterclockwise, the last column becomes the first row.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let cells = (0..self.width).rev().flat_map(|x| self.column(x).cloned()).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) is outside of a {}x{} grid", self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) is outside of a {}x{} grid", self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Prints one line per row, in the format the grid was parsed from if the cells print as their characters.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// abc
+    /// def
+    fn letters() -> Grid<char> {
+        Grid::from_str("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    fn turning_and_mirroring_move_the_cells_around() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        let turned = (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, grid);
+        assert_eq!((grid.rotate_clockwise().width(), grid.rotate_clockwise().height()), (2, 3));
+    }
+
+    #[test]
+    fn positions_off_the_grid_wrap_around_or_are_none() {
+        let grid = letters();
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!((grid.get((3, 0)), grid.get((-1, 0)), grid.get((0, 1))), (None, None, Some(&'d')));
+        assert_eq!([(3, 0), (-1, 0), (0, 2), (-4, -3), (5, 7)].map(|position| *grid.wrapping(position).unwrap()), ['a', 'c', 'a', 'f', 'f']);
+        assert_eq!(Grid::<char>::new(0, 0, Vec::new()).wrapping((0, 0)), None);
+        assert_eq!(Grid::<char>::new(3, 0, Vec::new()).wrapping((-1, 5)), None);
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = letters();
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4((1, 1)).collect::<Vec<_>>(), [(1, 0), (2, 1), (0, 1)]);
+        assert_eq!(grid.neighbours8((1, 0)).collect::<Vec<_>>(), [(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]);
+        assert_eq!(grid.neighbours8((2, 1)).count(), 3);
+        assert_eq!((grid.step((2, 1), Direction::East), grid.step((2, 1), Direction::North)), (None, Some((2, 0))));
+    }
+
+    #[test]
+    fn ragged_and_unknown_input_is_rejected() {
+        let error = Grid::from_str("ab\nabc\n", Some).unwrap_err();
+        assert_eq!((error.position, error.message.as_str()), (Position::new(2, 1), "expected 2 columns"));
+        let error = Grid::from_str("ab\na#\n", |c| c.is_alphabetic().then_some(c)).unwrap_err();
+        assert_eq!((error.position, error.text.as_str()), (Position::new(2, 2), "#"));
+        assert!(Grid::from_str("", Some).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// The files a directory given as input is searched for, in order.
+pub const INPUT_NAMES: [&str; 6] = ["input", "input.txt", "test", "test.txt", "example", "example.txt"];
+
+/// Where a puzzle input comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// Standard input, written `-`.
+    Stdin,
+    /// A file, or a directory holding one of the `INPUT_NAMES`.
+    Path(String),
+    /// The input itself, as given with `--input-str`.
+    Inline(String),
+}
+
+/// A puzzle input that has been read, with the name errors and answers refer to it by.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Input {
+    /// The file that was read, `-` for standard input or `<input-str>`.
+    pub name: String,
+    /// The normalized content.
+    pub text: String,
+}
+
+impl Input {
+    pub fn new(name: &str, text: &str) -> Self {
+        Input { name: name.to_string(), text: normalize(text) }
+    }
+}
+
+impl Source {
+    /// `-` is standard input, anything else a path.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(path.to_string()),
+        }
+    }
+
+    /// The name of the input before it is read; a directory only names the file in it once read.
+    pub fn name(&self) -> &str {
+        match self {
+            Source::Stdin => "-",
+            Source::Path(path) => path,
+            Source::Inline(_) => "<input-str>",
+        }
+    }
+
+    /// The input on the command line of a day's own binary: a path or `-` as the only argument, or
+    /// `--input-str <input>`.
+    pub fn from_args() -> Result<Source, String> {
+        let args: Vec<String> = env::args().skip(1).filter(|arg| !is_verbosity_flag(arg)).collect();
+        match &args[..] {
+            [flag, text] if flag == "--input-str" => Ok(Source::Inline(text.clone())),
+            [arg] => Ok(arg.strip_prefix("--input-str=").map_or_else(|| Source::from_arg(arg), |text| Source::Inline(text.to_string()))),
+            _ => Err("expected the puzzle input as a file or directory, `-` for standard input, or `--input-str <input>`".to_string()),
+        }
+    }
+
+    /// Reads the input. Standard input can only be read once.
+    pub fn read(&self) -> Result<Input, String> {
+        match self {
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map_err(|error| format!("-: {error}"))?;
+                Ok(Input::new(self.name(), &text))
+            }
+            Source::Path(path) => {
+                let file = resolve(path)?;
+                log::info!("Reading file {file}");
+                let text = fs::read_to_string(&file).map_err(|error| format!("{file}: {error}"))?;
+                Ok(Input::new(&file, &text))
+            }
+            Source::Inline(text) => Ok(Input::new(self.name(), text)),
+        }
+    }
+}
+
+/// The file to read for `path`: the path itself, or for a directory the first of the `INPUT_NAMES` in it.
+fn resolve(path: &str) -> Result<String, String> {
+    if !Path::new(path).is_dir() {
+        return Ok(path.to_string());
+    }
+    INPUT_NAMES
+        .iter()
+        .map(|name| Path::new(path).join(name))
+        .find(|file| file.is_file())
+        .map(|file| file.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("{path}: no {} in the directory", INPUT_NAMES.join(", ")))
+}
+
+/// Turns CRLF line endings into LF and drops the whitespace at the end of every line and the blank lines at the end,
+/// so an input saved by any editor or pasted into a terminal parses the same. A non-empty input ends with a newline.
+pub fn normalize(text: &str) -> String {
+    let normalized = text.lines().map(str::trim_end).collect::<Vec<&str>>().join("\n");
+    match normalized.trim_end() {
+        "" => String::new(),
+        trimmed => format!("{trimmed}\n"),
+    }
+}
+
+/// Whether a command line argument is one of the `-v`, `-vv`, ... verbosity flags.
+fn is_verbosity_flag(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v')
+}
+
+/// How many `v`s were passed on the command line, as in `-v` or `-vv`.
+pub fn verbosity() -> u8 {
+    env::args().skip(1).filter(|arg| is_verbosity_flag(arg)).map(|arg| arg.len() as u8 - 1).sum()
+}
+
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+pub fn non_empty_lines(input: &str) -> Vec<String> {
+    input.lines().filter(|line| !line.is_empty()).map(String::from).collect()
+}
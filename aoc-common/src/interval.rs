@@ -0,0 +1,223 @@
+/// The integers from `start` up to but excluding `end`. It is empty if `end <= start`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    /// The integers from `first` to `last`, both included.
+    pub fn inclusive(first: i64, last: i64) -> Self {
+        Interval { start: first, end: last + 1 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Some(Interval::new(self.start.max(other.start), self.end.min(other.end))).filter(|interval| !interval.is_empty())
+    }
+
+    /// The parts below `value` and from `value` on, either of which can be empty.
+    pub fn split_at(&self, value: i64) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.start, self.end.min(value));
+        let above = Interval::new(self.start.max(value), self.end);
+        (Some(below).filter(|interval| !interval.is_empty()), Some(above).filter(|interval| !interval.is_empty()))
+    }
+
+    pub fn offset(&self, delta: i64) -> Interval {
+        Interval::new(self.start + delta, self.end + delta)
+    }
+}
+
+/// A set of integers kept as sorted intervals that neither overlap nor touch, so equal sets compare equal.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    /// Sorts and merges the intervals, dropping empty ones.
+    fn normalized(mut intervals: Vec<Interval>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_by_key(|interval| interval.start);
+        let mut merged = Vec::<Interval>::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.end <= value);
+        self.intervals.get(i).is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = IntervalSet::normalized(intervals);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::normalized(self.intervals.iter().chain(&other.intervals).copied().collect())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::<Interval>::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(overlap) = a.intersection(&b) {
+                result.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The integers in this set that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::<Interval>::new();
+        let mut j = 0;
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < interval.end {
+                let removed = other.intervals[k];
+                if removed.start > start {
+                    result.push(Interval::new(start, removed.start));
+                }
+                start = start.max(removed.end);
+                k += 1;
+            }
+            if start < interval.end {
+                result.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The integers below `value` and those from `value` on.
+    pub fn split_at(&self, value: i64) -> (IntervalSet, IntervalSet) {
+        let (mut below, mut above) = (Vec::<Interval>::new(), Vec::<Interval>::new());
+        for interval in &self.intervals {
+            let (lower, upper) = interval.split_at(value);
+            below.extend(lower);
+            above.extend(upper);
+        }
+        (IntervalSet { intervals: below }, IntervalSet { intervals: above })
+    }
+
+    /// Adds `delta` to every integer of the set.
+    pub fn offset(&self, delta: i64) -> IntervalSet {
+        IntervalSet { intervals: self.intervals.iter().map(|interval| interval.offset(delta)).collect() }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        IntervalSet::normalized(vec![interval])
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        IntervalSet::normalized(intervals.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|&(start, end)| Interval::new(start, end)).collect()
+    }
+
+    /// The integers of the set between -20 and 20, to compare with the set operations on plain numbers.
+    fn members(set: &IntervalSet) -> Vec<i64> {
+        (-20..20).filter(|&value| set.contains(value)).collect()
+    }
+
+    #[test]
+    fn sets_are_merged_and_sorted() {
+        let merged = set(&[(5, 8), (0, 3), (3, 4), (7, 10), (12, 12)]);
+        assert_eq!(merged.intervals(), [Interval::new(0, 4), Interval::new(5, 10)]);
+        assert_eq!((merged.len(), merged.min(), merged.max()), (9, Some(0), Some(9)));
+        assert_eq!(merged, set(&[(0, 4), (5, 10)]));
+        assert!(set(&[(3, 3), (5, 1)]).is_empty());
+    }
+
+    #[test]
+    fn intersection_and_difference_match_the_members() {
+        let a = set(&[(-10, -5), (0, 6), (8, 15)]);
+        let b = set(&[(-7, 2), (4, 9), (14, 18)]);
+        let (a_members, b_members) = (members(&a), members(&b));
+        let intersection = a_members.iter().copied().filter(|value| b_members.contains(value)).collect::<Vec<_>>();
+        let difference = a_members.iter().copied().filter(|value| !b_members.contains(value)).collect::<Vec<_>>();
+        assert_eq!(members(&a.intersection(&b)), intersection);
+        assert_eq!(members(&a.difference(&b)), difference);
+        assert_eq!(a.intersection(&b).intervals(), [Interval::new(-7, -5), Interval::new(0, 2), Interval::new(4, 6), Interval::new(8, 9), Interval::new(14, 15)]);
+        assert_eq!(a.difference(&b).intervals(), [Interval::new(-10, -7), Interval::new(2, 4), Interval::new(9, 14)]);
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+        assert_eq!(a.union(&b).difference(&b).union(&a.intersection(&b)), a);
+    }
+
+    #[test]
+    fn sets_split_and_move() {
+        let (below, above) = set(&[(0, 4), (6, 9)]).split_at(7);
+        assert_eq!((below, above), (set(&[(0, 4), (6, 7)]), set(&[(7, 9)])));
+        assert_eq!(set(&[(0, 4)]).offset(-2), set(&[(-2, 2)]));
+        assert_eq!(Interval::inclusive(1, 3), Interval::new(1, 4));
+    }
+}
@@ -0,0 +1,31 @@
+pub mod box_set;
+pub mod cycle;
+pub mod direction;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod logging;
+pub mod math;
+pub mod numbers;
+pub mod oracle;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+
+pub use box_set::{BoxSet, HyperRect};
+pub use direction::{Axis, Coordinate, Direction, Direction8};
+pub use generate::{Generator, Rng};
+pub use graph::Graph;
+pub use grid::Grid;
+pub use input::{lines, non_empty_lines, normalize, verbosity, Input, Source};
+pub use interval::{Interval, IntervalSet};
+pub use numbers::{numbers, signed_numbers};
+pub use oracle::{Check, Oracle};
+pub use parse::{numbered_blocks, numbered_lines, ParseError, Position};
+pub use render::{Cell, Color, Render};
+pub use simulation::Simulation;
+pub use solution::{run, Solution, SolveError};
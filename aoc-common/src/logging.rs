@@ -0,0 +1,35 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes log records to standard error, so they never mix with the answers on standard output.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{:<5} {}: {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Only warnings are shown by default, `-v` adds the debug output of the solvers and `-vv` their traces.
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Installs the logger, later calls only change the level.
+pub fn init(verbosity: u8) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level(verbosity));
+}
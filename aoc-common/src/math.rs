@@ -0,0 +1,166 @@
+pub fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The least common multiple, or `None` if it does not fit. Dividing before multiplying keeps the intermediate
+/// result no larger than the answer.
+pub fn checked_lcm(a: u128, b: u128) -> Option<u128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The least common multiple of all values, 1 for none, or `None` if it does not fit.
+pub fn lcm_all(values: impl IntoIterator<Item = u128>) -> Option<u128> {
+    values.into_iter().try_fold(1, checked_lcm)
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `g = gcd(a, b) >= 0` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `a * b mod modulus` for values in `0..modulus`, without overflowing even if the product would.
+fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product.rem_euclid(modulus);
+    }
+    let (mut a, mut b, mut result) = (a, b, 0_i128);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+    result
+}
+
+/// Solves the system `x = residue (mod modulus)` for every `(residue, modulus)`, the moduli need not be coprime.
+/// Returns `(x, m)` with `x` in `0..m`, every solution being `x` plus a multiple of `m`, the lcm of the moduli.
+/// Returns `None` if the congruences contradict each other or `m` does not fit.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(x, m), &(residue, modulus)| {
+        assert!(modulus > 0, "modulus {modulus} is not positive");
+        let residue = residue.rem_euclid(modulus);
+        let (g, p, _) = extended_gcd(m, modulus);
+        if (residue - x) % g != 0 {
+            return None;
+        }
+        // x + m * k solves the new congruence for k = (residue - x) / g * p (mod modulus / g)
+        let reduced = modulus / g;
+        let k = mul_mod(((residue - x) / g).rem_euclid(reduced), p.rem_euclid(reduced), reduced);
+        let combined = (m / g).checked_mul(modulus)?;
+        Some(((x + mul_mod(m, k, combined)) % combined, combined))
+    })
+}
+
+/// The first time that is, for every `(offset, period)`, `offset` plus a multiple of `period`. This is when events
+/// that first happen at `offset` and then repeat every `period` all happen together.
+pub fn first_common_time(events: &[(i128, i128)]) -> Option<i128> {
+    let (x, m) = crt(events)?;
+    let start = events.iter().map(|(offset, _)| *offset).max().unwrap_or(0);
+    if x >= start {
+        return Some(x);
+    }
+    let periods = (start - x + m - 1) / m;
+    periods.checked_mul(m)?.checked_add(x)
+}
+
+/// The number of combinations of options `first_common_time_of_any` tries, if it fits.
+pub fn combinations_of_any(events: &[Vec<(i128, i128)>]) -> Option<usize> {
+    events.iter().try_fold(1_usize, |combinations, options| combinations.checked_mul(options.len()))
+}
+
+/// The earliest time all events happen together when each event can be any one of its `(offset, period)` options,
+/// trying every combination of options.
+pub fn first_common_time_of_any(events: &[Vec<(i128, i128)>]) -> Option<i128> {
+    let mut combinations = vec![Vec::<(i128, i128)>::new()];
+    for options in events {
+        combinations = combinations.iter()
+            .flat_map(|chosen| options.iter().map(move |option| [chosen.as_slice(), &[*option]].concat()))
+            .collect();
+    }
+    combinations.iter().filter_map(|chosen| first_common_time(chosen)).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcm_is_none_once_it_overflows() {
+        assert_eq!(checked_lcm(4, 6), Some(12));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+        assert_eq!(checked_lcm(u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(checked_lcm(1 << 127, 3), None);
+        assert_eq!(checked_lcm(u128::MAX, u128::MAX - 1), None);
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([1 << 120, 255]), Some(255 << 120));
+        assert_eq!(lcm_all([1 << 120, 257]), None);
+    }
+
+    #[test]
+    fn extended_gcd_and_inverse() {
+        assert_eq!(gcd(12, 18), 6);
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn crt_solves_congruences_with_shared_factors() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // 4 and 6 share a 2, which both residues agree on
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        // residues out of range and negative are taken modulo their modulus
+        assert_eq!(crt(&[(-1, 4), (17, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        // the lcm of the moduli overflows
+        assert_eq!(crt(&[(0, i128::MAX), (0, i128::MAX - 1)]), None);
+        // moduli of 61 and 64 bits, whose intermediate products do not fit
+        let (p, q) = ((1 << 61) - 1, (1 << 64) - 59);
+        let (x, m) = crt(&[(5, p), (q - 7, q)]).unwrap();
+        assert_eq!((x % p, x % q, m), (5, q - 7, p * q));
+    }
+
+    #[test]
+    fn common_times_start_after_every_offset() {
+        assert_eq!(first_common_time(&[(5, 4), (9, 6)]), Some(9));
+        assert_eq!(first_common_time(&[(5, 4), (11, 6)]), Some(17));
+        // the congruences alone are solved by 1, which is before the second event first happens
+        assert_eq!(first_common_time(&[(1, 2), (13, 3)]), Some(13));
+        assert_eq!(first_common_time(&[(0, 2), (1, 2)]), None);
+        assert_eq!(first_common_time_of_any(&[vec![(0, 2), (1, 2)], vec![(3, 4)]]), Some(3));
+        assert_eq!(combinations_of_any(&[vec![(0, 2), (1, 2)], vec![(3, 4); 3]]), Some(6));
+    }
+}
@@ -0,0 +1,29 @@
+use std::str::FromStr;
+use std::sync::OnceLock;
+use regex::Regex;
+
+use crate::parse::{parse_at, ParseError, Position};
+
+fn unsigned_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\d+").unwrap())
+}
+
+fn signed_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"-?\d+").unwrap())
+}
+
+fn parse_all<T: FromStr>(regex: &Regex, text: &str, position: Position) -> Result<Vec<T>, ParseError> {
+    regex.find_iter(text).map(|x| parse_at(x.as_str(), position.at_byte(text, x.start()))).collect()
+}
+
+/// Extracts every run of digits in `text`, which starts at `position`, ignoring any signs.
+pub fn numbers<T: FromStr>(text: &str, position: Position) -> Result<Vec<T>, ParseError> {
+    parse_all(unsigned_regex(), text, position)
+}
+
+/// Extracts every integer in `text`, which starts at `position`, including a leading `-`.
+pub fn signed_numbers<T: FromStr>(text: &str, position: Position) -> Result<Vec<T>, ParseError> {
+    parse_all(signed_regex(), text, position)
+}
@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use crate::solution::{Solution, SolveError};
+
+/// A slow but obviously correct way to compute what a day's solution computes with a shortcut. Checked side by side
+/// with the fast solution on generated inputs, it finds the inputs the shortcut gets wrong.
+pub trait Oracle: Solution {
+    /// Everything the reference can afford to recompute for the model, each with the fast solution's value.
+    fn checks(model: &Self::Model) -> Vec<Check>;
+}
+
+/// One value computed by both the fast solution and the reference.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub fast: String,
+    pub reference: String,
+}
+
+impl Check {
+    /// A fast solution without an answer disagrees with any reference.
+    pub fn new(name: impl Into<String>, fast: Result<impl Display, SolveError>, reference: impl Display) -> Self {
+        let fast = fast.map_or_else(|error| error.to_string(), |answer| answer.to_string());
+        Check { name: name.into(), fast, reference: reference.to_string() }
+    }
+
+    pub fn agrees(&self) -> bool {
+        self.fast == self.reference
+    }
+}
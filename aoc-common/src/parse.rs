@@ -0,0 +1,98 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use regex::{Captures, Regex};
+
+/// A place in the puzzle input. Lines and columns start at 1, columns count characters.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Position { line, column }
+    }
+
+    pub fn start_of(line: usize) -> Self {
+        Position { line, column: 1 }
+    }
+
+    /// The position of the byte `offset` of `text`, which starts at this position.
+    pub fn at_byte(self, text: &str, offset: usize) -> Self {
+        Position { line: self.line, column: self.column + text[..offset].chars().count() }
+    }
+}
+
+/// Bad puzzle input, pointing at the offending text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub position: Position,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(position: Position, text: &str, message: impl Into<String>) -> Self {
+        ParseError { file: None, position, text: text.to_string(), message: message.into() }
+    }
+
+    /// Records which file the input came from, the parsers themselves only ever see its content.
+    pub fn in_file(self, file: &str) -> Self {
+        ParseError { file: Some(file.to_string()), ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(f, "{}:{}: {}: `{}`", self.position.line, self.position.column, self.message, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The non-empty lines of `input`, together with their line numbers.
+pub fn numbered_lines(input: &str) -> Vec<(usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i + 1, line)).filter(|(_, line)| !line.is_empty()).collect()
+}
+
+/// Splits the input into blocks separated by one or more blank lines, keeping line numbers.
+pub fn numbered_blocks(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut result = Vec::<Vec<(usize, &str)>>::new();
+    let mut current_block = Vec::<(usize, &str)>::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            if !current_block.is_empty() {
+                result.push(current_block);
+                current_block = Vec::new();
+            }
+            continue;
+        }
+        current_block.push((i + 1, line));
+    }
+    if !current_block.is_empty() {
+        result.push(current_block);
+    }
+    result
+}
+
+/// Matches the whole of `regex` against `text`, which starts at `position`.
+pub fn captures<'a>(regex: &Regex, text: &'a str, position: Position) -> Result<Captures<'a>, ParseError> {
+    regex.captures(text).ok_or_else(|| ParseError::new(position, text, format!("expected `{}`", regex.as_str())))
+}
+
+/// Parses `text`, which starts at `position`.
+pub fn parse_at<T: FromStr>(text: &str, position: Position) -> Result<T, ParseError> {
+    text.parse::<T>().map_err(|_| ParseError::new(position, text, format!("invalid {}", std::any::type_name::<T>())))
+}
+
+/// Parses the named group of a match made on `text`, which starts at `position`.
+pub fn parse_group<T: FromStr>(captures: &Captures, name: &str, text: &str, position: Position) -> Result<T, ParseError> {
+    let group = captures.name(name).ok_or_else(|| ParseError::new(position, text, format!("missing {name}")))?;
+    parse_at(group.as_str(), position.at_byte(text, group.start()))
+}
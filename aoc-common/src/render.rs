@@ -0,0 +1,163 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::solution::{Solution, SolveError};
+
+/// A colour as red, green and blue.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const DARK: Color = Color(40, 40, 48);
+    pub const GREY: Color = Color(120, 120, 130);
+    pub const WHITE: Color = Color(230, 230, 230);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const ORANGE: Color = Color(240, 140, 30);
+    pub const YELLOW: Color = Color(250, 210, 40);
+    pub const GREEN: Color = Color(60, 180, 75);
+    pub const BLUE: Color = Color(50, 110, 220);
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// How one cell of a grid is drawn: a glyph in the foreground colour on the background colour.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub foreground: Color,
+    pub background: Color,
+}
+
+impl Cell {
+    /// The glyph on a dark background.
+    pub fn new(glyph: char, foreground: Color) -> Self {
+        Cell { glyph, foreground, background: Color::DARK }
+    }
+
+    pub fn on(self, background: Color) -> Self {
+        Cell { background, ..self }
+    }
+}
+
+/// A picture of what a day's solution found, drawn over its grid: the loop, the path, the tiles it counts.
+pub trait Render: Solution {
+    /// The picture behind the answer to `part`, or why there is none, which is the part's own reason.
+    fn render(model: &Self::Model, part: u8) -> Result<Grid<Cell>, SolveError>;
+}
+
+/// Lines of text in one colour, padded with spaces to the longest line.
+pub fn text(lines: &[String], color: Color) -> Grid<Cell> {
+    let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let cells = lines.iter()
+        .flat_map(|line| line.chars().chain(std::iter::repeat(' ')).take(width))
+        .map(|glyph| Cell::new(glyph, color))
+        .collect();
+    Grid::new(width, lines.len(), cells)
+}
+
+/// The ways a picture can be written.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Text with the colours as 24-bit escape codes, for a terminal.
+    Ansi,
+    Svg,
+    Png,
+}
+
+impl Format {
+    /// The format a file name's extension asks for, text for a terminal unless it is `.svg` or `.png`.
+    pub fn from_path(path: &str) -> Format {
+        match Path::new(path).extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("svg") => Format::Svg,
+            Some("png") => Format::Png,
+            _ => Format::Ansi,
+        }
+    }
+}
+
+/// The picture in the format, as the bytes of the file.
+pub fn encode(picture: &Grid<Cell>, format: Format) -> Vec<u8> {
+    match format {
+        Format::Ansi => ansi(picture).into_bytes(),
+        Format::Svg => svg(picture).into_bytes(),
+        Format::Png => png(picture),
+    }
+}
+
+/// One line per row. The colours are only switched where they change, and reset at the end of every line.
+pub fn ansi(picture: &Grid<Cell>) -> String {
+    let mut text = String::new();
+    for row in picture.rows() {
+        let mut colors = None;
+        for cell in row {
+            if colors != Some((cell.foreground, cell.background)) {
+                let (Color(r, g, b), Color(br, bg, bb)) = (cell.foreground, cell.background);
+                write!(text, "\x1b[38;2;{r};{g};{b};48;2;{br};{bg};{bb}m").unwrap();
+                colors = Some((cell.foreground, cell.background));
+            }
+            text.push(cell.glyph);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// The size of a cell in an SVG, in user units.
+const SVG_CELL: usize = 12;
+
+/// A square for every cell and its glyph on top, in a monospace font.
+pub fn svg(picture: &Grid<Cell>) -> String {
+    let (width, height) = (picture.width() * SVG_CELL, picture.height() * SVG_CELL);
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n");
+    writeln!(svg, "<g font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">", SVG_CELL - 2).unwrap();
+    for ((x, y), cell) in picture.iter() {
+        let (left, top) = (x * SVG_CELL, y * SVG_CELL);
+        writeln!(svg, "<rect x=\"{left}\" y=\"{top}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{}\"/>", cell.background.hex()).unwrap();
+        if !cell.glyph.is_whitespace() {
+            let glyph = match cell.glyph {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                glyph => glyph.to_string(),
+            };
+            let (middle, center) = (left + SVG_CELL / 2, top + SVG_CELL / 2);
+            writeln!(svg, "<text x=\"{middle}\" y=\"{center}\" fill=\"{}\">{glyph}</text>", cell.foreground.hex()).unwrap();
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// The size of a cell in a PNG, in pixels.
+const PNG_CELL: usize = 5;
+
+/// A square of the background colour for every cell, with a smaller square of the foreground colour in its middle
+/// unless the glyph is blank. Glyphs are too small to read at this size, the colours carry the picture. An empty
+/// picture is a single black pixel, PNGs cannot be empty.
+pub fn png(picture: &Grid<Cell>) -> Vec<u8> {
+    let (width, height) = ((picture.width() * PNG_CELL).max(1), (picture.height() * PNG_CELL).max(1));
+    let mut pixels = vec![0_u8; width * height * 3];
+    for ((x, y), cell) in picture.iter() {
+        for dy in 0..PNG_CELL {
+            for dx in 0..PNG_CELL {
+                let inner = (1..PNG_CELL - 1).contains(&dx) && (1..PNG_CELL - 1).contains(&dy);
+                let Color(r, g, b) = if inner && !cell.glyph.is_whitespace() { cell.foreground } else { cell.background };
+                let offset = ((y * PNG_CELL + dy) * width + x * PNG_CELL + dx) * 3;
+                pixels[offset..offset + 3].copy_from_slice(&[r, g, b]);
+            }
+        }
+    }
+    let mut bytes = Vec::<u8>::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    // writing to memory cannot fail, and the size matches the pixels
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+    bytes
+}
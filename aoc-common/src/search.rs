@@ -0,0 +1,161 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A cheapest path found by `dijkstra` or `astar`, from the start it began at to the goal it reached.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        self.states.first().unwrap()
+    }
+
+    pub fn goal(&self) -> &S {
+        self.states.last().unwrap()
+    }
+}
+
+/// Finds the cheapest path from any of `starts` to a state that `is_goal`, exploring the graph lazily through
+/// `neighbours`, which lists the states reachable in one move and what the move costs.
+pub fn dijkstra<S, C, N, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: N,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, but visits states in order of their cost plus `heuristic`, an estimate of the remaining cost.
+/// The path is only guaranteed to be the cheapest if the heuristic never overestimates.
+pub fn astar<S, C, N, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: N,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // States are numbered in the order they are discovered, the heap and the parent links refer to those numbers.
+    let mut states = Vec::<S>::new();
+    let mut indices = HashMap::<S, usize>::new();
+    let mut costs = Vec::<C>::new();
+    let mut parents = Vec::<Option<usize>>::new();
+    let mut queue = BinaryHeap::<Reverse<(C, C, usize)>>::new();
+    for start in starts {
+        if indices.contains_key(&start) {
+            continue;
+        }
+        indices.insert(start.clone(), states.len());
+        queue.push(Reverse((heuristic(&start), C::default(), states.len())));
+        states.push(start);
+        costs.push(C::default());
+        parents.push(None);
+    }
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > costs[index] {
+            // a cheaper way to this state was queued after this one
+            continue;
+        }
+        if is_goal(&states[index]) {
+            let mut path = vec![index];
+            while let Some(parent) = parents[*path.last().unwrap()] {
+                path.push(parent);
+            }
+            let states = path.into_iter().rev().map(|index| states[index].clone()).collect();
+            return Some(Path { cost, states });
+        }
+        for (next, step_cost) in neighbours(&states[index]) {
+            let next_cost = cost + step_cost;
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    if next_cost >= costs[next_index] {
+                        continue;
+                    }
+                    costs[next_index] = next_cost;
+                    parents[next_index] = Some(index);
+                    next_index
+                }
+                Entry::Vacant(entry) => {
+                    let next_index = states.len();
+                    states.push(entry.key().clone());
+                    entry.insert(next_index);
+                    costs.push(next_cost);
+                    parents.push(Some(index));
+                    next_index
+                }
+            };
+            queue.push(Reverse((next_cost + heuristic(&states[next_index]), next_cost, next_index)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The moves between lettered states, where going through `b` is the long way round but has the cheapest first move.
+    fn moves(state: &char) -> Vec<(char, u32)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 4)],
+            'b' => vec![('d', 10)],
+            'c' => vec![('d', 2), ('a', 1)],
+            'd' => vec![('e', 1)],
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn the_path_is_rebuilt_from_start_to_goal() {
+        let path = dijkstra(['a'], moves, |&state| state == 'e').unwrap();
+        assert_eq!((path.cost, path.states.as_slice()), (7, &['a', 'c', 'd', 'e'][..]));
+        assert_eq!((*path.start(), *path.goal()), ('a', 'e'));
+        // a heuristic that never overestimates finds the same path
+        let remaining = |state: &char| match state { 'a' => 7, 'b' => 11, 'c' => 3, 'd' => 1, _ => 0 };
+        assert_eq!(astar(['a'], moves, remaining, |&state| state == 'e'), Some(path));
+    }
+
+    #[test]
+    fn paths_start_at_the_cheapest_start_and_can_be_empty() {
+        let path = dijkstra(['a', 'c'], moves, |&state| state == 'd').unwrap();
+        assert_eq!((path.cost, path.states.as_slice()), (2, &['c', 'd'][..]));
+        let path = dijkstra(['d'], moves, |&state| state == 'd').unwrap();
+        assert_eq!((path.cost, path.states.as_slice()), (0, &['d'][..]));
+        assert_eq!(dijkstra(['e'], moves, |&state| state == 'a'), None);
+    }
+
+    #[test]
+    fn grid_paths_go_round_walls() {
+        // a wall along x = 2 with a gap at y = 4, on a 5 by 5 grid
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)].into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && (x != 2 || y == 4))
+                .map(|position| (position, 1))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+        let path = astar([(0, 0)], neighbours, manhattan, |&position| position == (4, 0)).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+        assert!(path.states.windows(2).all(|pair| (pair[0].0 - pair[1].0).abs() + (pair[0].1 - pair[1].1).abs() == 1));
+        assert!(path.states.contains(&(2, 4)));
+    }
+}
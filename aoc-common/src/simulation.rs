@@ -0,0 +1,23 @@
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::Cell;
+
+/// A day whose solution plays out step by step, so it can be watched one state at a time. The states start from the
+/// input rather than from the solution's model, which may already be the end of the simulation.
+pub trait Simulation {
+    /// Everything a step needs. Parts of the input that never change are best shared, every state is kept.
+    type State: Clone;
+
+    /// The state the input describes, before the first step.
+    fn start(input: &str) -> Result<Self::State, ParseError>;
+
+    /// The state after one more step and what happened during it, one line per event, or `None` once nothing
+    /// happens anymore. Breakpoints stop at the first step with an event that mentions them.
+    fn step(state: &Self::State) -> Option<(Self::State, Vec<String>)>;
+
+    /// What the state looks like.
+    fn picture(state: &Self::State) -> Grid<Cell>;
+
+    /// The numbers worth knowing about the state, one per line.
+    fn details(state: &Self::State) -> Vec<String>;
+}
@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::process::exit;
+
+use crate::input::Source;
+use crate::parse::ParseError;
+
+/// A day's puzzle: the input is parsed once into a model, which both parts then solve.
+pub trait Solution {
+    type Model;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError>;
+    fn part1(model: &Self::Model) -> Result<Self::Answer1, SolveError>;
+    fn part2(model: &Self::Model) -> Result<Self::Answer2, SolveError>;
+}
+
+/// Why a part has no answer for an input that parsed, such as a path that does not exist or a module that only
+/// the other part needs. It is about the input as a whole, so unlike a `ParseError` it has no position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        SolveError { message: message.into() }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no answer: {}", self.message)
+    }
+}
+
+impl Error for SolveError {}
+
+/// Solves both parts for the input given on the command line and prints the answers.
+pub fn run<S: Solution>() {
+    crate::logging::init(crate::verbosity());
+    let input = Source::from_args().and_then(|source| source.read()).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        exit(1);
+    });
+    let model = S::parse(&input.text).unwrap_or_else(|error| {
+        eprintln!("{}", error.in_file(&input.name));
+        exit(1);
+    });
+    let mut failed = false;
+    match S::part1(&model) {
+        Ok(answer) => println!("Part 1: {answer}"),
+        Err(error) => {
+            eprintln!("Part 1: {error}");
+            failed = true;
+        }
+    }
+    match S::part2(&model) {
+        Ok(answer) => println!("Part 2: {answer}"),
+        Err(error) => {
+            eprintln!("Part 2: {error}");
+            failed = true;
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use serde::{Deserialize, Serialize};
+
+use crate::pool::{Outcome, Status};
+
+/// An answer known to be right for one part of a day on one input.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Recorded {
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    /// Where the input was read from when the answer was recorded, only to tell the entries apart.
+    pub input_path: String,
+    pub answer: String,
+}
+
+/// The answers file, a TOML list of `[[answer]]` tables.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    answers: Vec<Recorded>,
+}
+
+impl Answers {
+    /// Reads the answers file, a missing file has no answers yet.
+    pub fn load(path: &str) -> Result<Answers, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map_err(|error| format!("{path}: {error}")),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(format!("{path}: {error}")),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, toml::to_string(self).unwrap()).map_err(|error| format!("{path}: {error}"))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input_hash: &str) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|recorded| recorded.day == day && recorded.part == part && recorded.input_hash == input_hash)
+            .map(|recorded| recorded.answer.as_str())
+    }
+
+    /// Records an answer, replacing the one recorded for the same day, part and input.
+    pub fn record(&mut self, recorded: Recorded) {
+        self.answers.retain(|other| (other.day, other.part, &other.input_hash) != (recorded.day, recorded.part, &recorded.input_hash));
+        self.answers.push(recorded);
+        self.answers.sort_by(|a, b| (a.day, a.part, &a.input_path).cmp(&(b.day, b.part, &b.input_path)));
+    }
+}
+
+/// Identifies an input by its content, so answers follow the input rather than the file name. This is 64 bit FNV-1a,
+/// which unlike the standard library hashers gives the same value on every build.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    format!("{hash:016x}")
+}
+
+/// How a solved part compares with the answers file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    /// Nothing was recorded for this part and input.
+    Unknown,
+    /// Nothing was recorded, so the answer just was.
+    Recorded,
+    Failed(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. } | Verdict::Failed(_))
+    }
+}
+
+pub fn verdict(outcome: &Outcome, expected: Option<&str>) -> Verdict {
+    match (&outcome.status, &outcome.answer, expected) {
+        (Status::Failed(error), _, _) => Verdict::Failed(error.clone()),
+        (Status::Solved, Some(answer), Some(expected)) if answer == expected => Verdict::Correct,
+        (Status::Solved, _, Some(expected)) => Verdict::Wrong { expected: expected.to_string() },
+        (Status::Solved, _, None) => Verdict::Unknown,
+    }
+}
+
+pub fn table(checks: &[(Outcome, Verdict)]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:<20}  {}\n", "Day", "Part", "Answer", "Status");
+    for (outcome, verdict) in checks {
+        let status = match verdict {
+            Verdict::Correct => "ok".to_string(),
+            Verdict::Wrong { expected } => format!("WRONG, expected {expected}"),
+            Verdict::Unknown => "no recorded answer".to_string(),
+            Verdict::Recorded => "recorded".to_string(),
+            Verdict::Failed(error) => format!("failed: {error}"),
+        };
+        table += &format!("{:>3}  {:>4}  {:<20}  {status}\n", outcome.day, outcome.part, outcome.answer.as_deref().unwrap_or("-"));
+    }
+    table
+}
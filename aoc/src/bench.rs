@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use aoc_common::ParseError;
+use serde::Serialize;
+
+use crate::registry::{Day, Timings};
+
+/// The median timings of a day over several runs, in milliseconds.
+#[derive(Serialize)]
+pub struct Benchmark {
+    pub day: u8,
+    pub input_path: String,
+    pub runs: usize,
+    pub parse_ms: f64,
+    pub part1_ms: f64,
+    pub part2_ms: f64,
+}
+
+impl Benchmark {
+    pub fn total_ms(&self) -> f64 {
+        self.parse_ms + self.part1_ms + self.part2_ms
+    }
+}
+
+fn median_ms(timings: &[Timings], stage: impl Fn(&Timings) -> Duration) -> f64 {
+    let mut values = timings.iter().map(|timing| stage(timing).as_secs_f64() * 1000.0).collect::<Vec<f64>>();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values[values.len() / 2]
+}
+
+pub fn measure(day: &Day, input: &str, input_path: &str, runs: usize) -> Result<Benchmark, ParseError> {
+    let timings = (0..runs.max(1)).map(|_| (day.time)(input)).collect::<Result<Vec<Timings>, ParseError>>()?;
+    Ok(Benchmark {
+        day: day.number,
+        input_path: input_path.to_string(),
+        runs: timings.len(),
+        parse_ms: median_ms(&timings, |timing| timing.parse),
+        part1_ms: median_ms(&timings, |timing| timing.part1),
+        part2_ms: median_ms(&timings, |timing| timing.part2),
+    })
+}
+
+pub fn table(benchmarks: &[Benchmark]) -> String {
+    let mut table = format!("{:>3}  {:<24}  {:>10}  {:>10}  {:>10}  {:>10}\n", "Day", "Input", "Parse ms", "Part 1 ms", "Part 2 ms", "Total ms");
+    for benchmark in benchmarks {
+        table += &format!(
+            "{:>3}  {:<24}  {:>10.3}  {:>10.3}  {:>10.3}  {:>10.3}\n",
+            benchmark.day, benchmark.input_path, benchmark.parse_ms, benchmark.part1_ms, benchmark.part2_ms, benchmark.total_ms()
+        );
+    }
+    table
+}
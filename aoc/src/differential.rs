@@ -0,0 +1,44 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::pool::panic_message;
+use crate::registry::{CheckFn, Day};
+
+/// A generated input on which a day's solution and its reference disagree.
+#[derive(Clone, Debug)]
+pub struct Counterexample {
+    pub day: u8,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    /// The checks that disagree, or the parse error or panic that kept them from running.
+    pub problems: Vec<String>,
+}
+
+/// Everything that goes wrong when checking the input against the reference.
+fn problems(check: CheckFn, input: &str) -> Vec<String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| check(input))) {
+        Ok(Ok(checks)) => checks
+            .iter()
+            .filter(|check| !check.agrees())
+            .map(|check| format!("{}: the solution says {}, the reference {}", check.name, check.fast, check.reference))
+            .collect(),
+        Ok(Err(error)) => vec![error.to_string()],
+        Err(payload) => vec![format!("panicked: {}", panic_message(payload.as_ref()))],
+    }
+}
+
+/// Checks a day that has a reference on the generated inputs of `size` for every seed below `seeds`. The first
+/// disagreement is shrunk to the smallest size at which any of the seeds still disagrees, and of those to the
+/// shortest input. Returns nothing for a day without a reference.
+pub fn find_counterexample(day: &Day, seeds: u64, size: usize) -> Option<Counterexample> {
+    let check = day.check?;
+    let disagreement = |seed: u64, size: usize| {
+        let input = (day.generate)(seed, size);
+        let problems = problems(check, &input);
+        (!problems.is_empty()).then_some(Counterexample { day: day.number, seed, size, input, problems })
+    };
+    let found = (0..seeds).find_map(|seed| disagreement(seed, size))?;
+    (1..found.size)
+        .find_map(|size| (0..seeds).filter_map(|seed| disagreement(seed, size)).min_by_key(|counterexample| counterexample.input.len()))
+        .or(Some(found))
+}
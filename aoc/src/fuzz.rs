@@ -0,0 +1,143 @@
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::Rng;
+
+use crate::pool::panic_message;
+use crate::registry::Day;
+
+/// Some days recurse deeply, the inputs are solved on a thread with the stack of a main thread and then some.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Text that is likely to trip up a parser or a solver, mixed into the mutated inputs.
+const NUMBERS: [&str; 10] = ["0", "1", "-1", "2", "-", "65536", "4294967296", "9223372036854775807", "18446744073709551616", "99999999999999999999999"];
+/// Characters the inputs are made of, and two they never are: a digit of another script and an accented letter.
+const PUNCTUATION: &str = " \n\n,.:;#=-~@<>{}()|/\\SA٣é";
+
+/// Feeds arbitrary bytes to a day the way the fuzz targets do. The parser sees them if they are text, and both parts
+/// see whatever it accepts. Errors are fine, panics are bugs.
+pub fn solve(day: &Day, data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = (day.solve)(input, &[1, 2], &mut |_, _, _| {});
+    }
+}
+
+/// What went wrong on an input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Failure {
+    Panic(String),
+    /// Still not done after the timeout.
+    Hang,
+}
+
+/// An input a day panics or hangs on.
+#[derive(Clone, Debug)]
+pub struct Crash {
+    pub day: u8,
+    pub input: String,
+    pub failure: Failure,
+}
+
+/// Solves the input on a thread of its own, giving up on it after `timeout`. A thread that hangs is left running.
+pub fn failure(day: &'static Day, input: &str, timeout: Duration) -> Option<Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    thread::Builder::new()
+        .name(format!("fuzz day {}", day.number))
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| solve(day, input.as_bytes())));
+            let _ = sender.send(solved.err().map(|payload| Failure::Panic(panic_message(payload.as_ref()))));
+        })
+        .unwrap();
+    receiver.recv_timeout(timeout).unwrap_or(Some(Failure::Hang))
+}
+
+/// A few random edits of the input: characters replaced, spans deleted or repeated, numbers of all sizes put in,
+/// lines dropped or doubled, or the end cut off.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars = input.chars().collect::<Vec<char>>();
+    for _ in 0..rng.between(1, 4) {
+        let at = rng.below(chars.len() + 1);
+        let end = (at + rng.below(8) + 1).min(chars.len());
+        match rng.below(7) {
+            0 if at < chars.len() => {
+                let palette = PUNCTUATION.chars().chain(chars.iter().copied()).collect::<Vec<char>>();
+                chars[at] = *rng.choose(&palette);
+            }
+            1 => {
+                chars.drain(at..end);
+            }
+            2 => {
+                let span = chars[at..end].to_vec();
+                chars.splice(at..at, span);
+            }
+            3 => {
+                let number = if rng.chance(0.5) { rng.choose(&NUMBERS).to_string() } else { rng.between(-1000, 1000).to_string() };
+                chars.splice(at..end.min(at + number.len()), number.chars());
+            }
+            4 => chars.truncate(at),
+            _ => {
+                let mut lines = chars.iter().collect::<String>().split('\n').map(str::to_string).collect::<Vec<String>>();
+                let line = rng.below(lines.len());
+                if rng.chance(0.5) {
+                    lines.remove(line);
+                } else {
+                    lines.insert(line, lines[line].clone());
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// A smaller input that fails the same way, found by dropping lines and then characters as long as it still does.
+fn shrink(day: &'static Day, crash: Crash, timeout: Duration) -> Crash {
+    let fails = |input: &str| failure(day, input, timeout).is_some_and(|failure| mem::discriminant(&failure) == mem::discriminant(&crash.failure));
+    let mut input = crash.input.clone();
+    let mut lines = input.split_inclusive('\n').map(str::to_string).collect::<Vec<String>>();
+    let mut line = 0;
+    while line < lines.len() {
+        let shorter = lines.iter().enumerate().filter(|&(i, _)| i != line).map(|(_, line)| line.as_str()).collect::<String>();
+        if fails(&shorter) {
+            lines.remove(line);
+            input = shorter;
+        } else {
+            line += 1;
+        }
+    }
+    let mut at = 0;
+    while at < input.len() && input.len() < 2000 {
+        let mut shorter = input.clone();
+        shorter.remove(at);
+        if fails(&shorter) {
+            input = shorter;
+        } else {
+            at += input[at..].chars().next().unwrap().len_utf8();
+        }
+    }
+    let failure = failure(day, &input, timeout).unwrap_or(crash.failure);
+    Crash { input, failure, ..crash }
+}
+
+/// Solves `runs` mutations of the day's generated inputs, starting from `seed`, and returns the first one it panics
+/// or hangs on, shrunk. A hang is only shrunk when `timeout` is short enough to do it in a reasonable time.
+pub fn find_crash(day: &'static Day, runs: u64, seed: u64, timeout: Duration) -> Option<Crash> {
+    for run in 0..runs {
+        let mut rng = Rng::new(seed.wrapping_add(run));
+        let generated = (day.generate)(rng.next_u64(), 1 + rng.below(5));
+        let input = mutate(&mut rng, &generated);
+        if let Some(failure) = failure(day, &input, timeout) {
+            let crash = Crash { day: day.number, input, failure };
+            if crash.failure == Failure::Hang && timeout > Duration::from_millis(500) {
+                return Some(crash);
+            }
+            return Some(shrink(day, crash, timeout));
+        }
+    }
+    None
+}
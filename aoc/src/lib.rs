@@ -0,0 +1,9 @@
+pub mod answers;
+pub mod bench;
+pub mod differential;
+pub mod fuzz;
+pub mod output;
+pub mod pool;
+pub mod registry;
+pub mod stepper;
+pub mod store;
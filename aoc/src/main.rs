@@ -0,0 +1,577 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::panic;
+use std::process::exit;
+use std::thread;
+use std::time::Duration;
+
+use aoc::answers::{self, Answers, Recorded, Verdict};
+use aoc::bench::{self, Benchmark};
+use aoc::differential;
+use aoc::fuzz::{self, Failure};
+use aoc::output::{Answer, Format};
+use aoc::pool::{self, Outcome, Status};
+use aoc::registry::{self, Day};
+use aoc::stepper::{self, Stepper};
+use aoc::store::{self, HttpFetcher, Store};
+use aoc_common::{render, Input, Source};
+use clap::{ArgAction, Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(about = "Runs the advent of code 2023 solutions")]
+struct Cli {
+    /// Shows the solvers' debug output, repeat for their traces
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Where fetched puzzle inputs are kept
+    #[arg(long, global = true, env = "AOC_STORE", default_value = "inputs")]
+    store: String,
+    /// Whose inputs are taken from the store, and fetched with the session cookie in `AOC_SESSION`
+    #[arg(long, global = true, env = "AOC_USER", default_value = "default")]
+    user: String,
+    /// The puzzle server missing inputs are fetched from
+    #[arg(long, global = true, env = "AOC_BASE_URL", default_value = store::DEFAULT_BASE_URL)]
+    base_url: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs one day, or every day that has an input file
+    Run(RunArgs),
+    /// Times parsing and both parts of one day, or of every day that has an input file
+    Bench(BenchArgs),
+    /// Re-runs one day, or every day that has an input file, and compares the answers with the recorded ones
+    Verify(VerifyArgs),
+    /// Writes a random input for one day, always the same one for the same seed and size
+    Generate(GenerateArgs),
+    /// Compares the days that have a slow reference solution with it on generated inputs
+    Differential(DifferentialArgs),
+    /// Feeds mutated inputs to one day, or to every day, and reports the first one it panics or hangs on
+    Fuzz(FuzzArgs),
+    /// Steps through the simulation of one day in the terminal, back and forth and to breakpoints
+    Step(StepArgs),
+    /// Downloads the inputs of one day, or of every day, into the store unless they are there already
+    Fetch(FetchArgs),
+    /// Lists the registered days and their default inputs
+    List,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run
+    #[arg(long, required_unless_present = "all", conflicts_with = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Part to run, both parts are run if omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Puzzle input: a file, `-` for standard input, or a directory holding an `input`, `test` or `example` file.
+    /// Defaults to `aoc-<day>/input` or `aoc-<day>/input.txt`
+    #[arg(long, conflicts_with = "all")]
+    input: Option<String>,
+    /// The puzzle input itself, instead of a file
+    #[arg(long, conflicts_with_all = ["all", "input"])]
+    input_str: Option<String>,
+    /// Runs every day that has a default input file, in parallel, and prints a summary table
+    #[arg(long)]
+    all: bool,
+    /// How many days `--all` solves at once, defaults to the number of CPUs
+    #[arg(long, requires = "all", value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: Option<u64>,
+    /// How the answers are printed
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Draws the solution of the part, or of part 2 if both are run, over the input into this file: a `.png`, an
+    /// `.svg`, or text coloured for a terminal for any other name, `-` for standard output
+    #[arg(long, conflicts_with = "all")]
+    render: Option<String>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to time
+    #[arg(long, required_unless_present = "all", conflicts_with = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Puzzle input: a file, `-` for standard input, or a directory holding an `input`, `test` or `example` file.
+    /// Defaults to `aoc-<day>/input` or `aoc-<day>/input.txt`
+    #[arg(long, conflicts_with = "all")]
+    input: Option<String>,
+    /// The puzzle input itself, instead of a file
+    #[arg(long, conflicts_with_all = ["all", "input"])]
+    input_str: Option<String>,
+    /// Times every day that has a default input file
+    #[arg(long)]
+    all: bool,
+    /// How many times each day is run, the median time is reported
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+    /// Also writes the results as JSON to this file, `-` for standard output
+    #[arg(long)]
+    json: Option<String>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to verify
+    #[arg(long, required_unless_present = "all", conflicts_with = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Puzzle input: a file, `-` for standard input, or a directory holding an `input`, `test` or `example` file.
+    /// Defaults to `aoc-<day>/input` or `aoc-<day>/input.txt`
+    #[arg(long, conflicts_with = "all")]
+    input: Option<String>,
+    /// The puzzle input itself, instead of a file
+    #[arg(long, conflicts_with_all = ["all", "input"])]
+    input_str: Option<String>,
+    /// Verifies every day that has a default input file
+    #[arg(long)]
+    all: bool,
+    /// The file the known answers are kept in
+    #[arg(long, default_value = "answers.toml")]
+    answers: String,
+    /// Records the answers of parts that have none recorded for their input yet
+    #[arg(long)]
+    record: bool,
+    /// How many days are solved at once, defaults to the number of CPUs
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: Option<u64>,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to write an input for
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Seed for the random numbers
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// How large the input is, what it counts depends on the day
+    #[arg(long, default_value_t = 10)]
+    size: usize,
+    /// File to write the input to, standard output if omitted
+    #[arg(long)]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct DifferentialArgs {
+    /// Day to check, every day with a reference if omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// How many seeds to generate inputs from
+    #[arg(long, default_value_t = 100)]
+    seeds: u64,
+    /// Size of the generated inputs, counterexamples are shrunk from there
+    #[arg(long, default_value_t = 8)]
+    size: usize,
+}
+
+#[derive(Args)]
+struct FuzzArgs {
+    /// Day to fuzz, every day if omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// How many inputs each day is fed
+    #[arg(long, default_value_t = 1000)]
+    runs: u64,
+    /// Seed of the first input, the others follow it
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// How long a day may take on one input before it counts as hanging
+    #[arg(long, default_value_t = 2000)]
+    timeout_ms: u64,
+}
+
+#[derive(Args)]
+struct StepArgs {
+    /// Day to step through
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Puzzle input: a file, `-` for standard input, or a directory holding an `input`, `test` or `example` file.
+    /// Defaults to `aoc-<day>/input` or `aoc-<day>/input.txt`
+    #[arg(long)]
+    input: Option<String>,
+    /// The puzzle input itself, instead of a file
+    #[arg(long, conflicts_with = "input")]
+    input_str: Option<String>,
+    /// Stops running at the first step with an event containing this text, can be given more than once
+    #[arg(long = "break")]
+    breakpoints: Vec<String>,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// Day to fetch
+    #[arg(long, required_unless_present = "all", conflicts_with = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Fetches every day
+    #[arg(long)]
+    all: bool,
+}
+
+/// Where the days' inputs are found when none is given: next to the day's crate, or in the store, which fetches the
+/// missing ones when there is a session cookie to fetch them with.
+struct Inputs {
+    store: Store,
+    user: String,
+    fetcher: Option<HttpFetcher>,
+}
+
+impl Inputs {
+    fn new(cli: &Cli) -> Self {
+        let session = env::var("AOC_SESSION").ok().filter(|session| !session.trim().is_empty());
+        let fetcher = session.map(|session| HttpFetcher::new(&cli.base_url, &session, store::DEFAULT_INTERVAL));
+        Inputs { store: Store::new(&cli.store), user: cli.user.clone(), fetcher }
+    }
+
+    /// The input next to the day's crate or in the store, without fetching anything.
+    fn local(&self, day: &Day) -> Option<String> {
+        ["input", "input.txt"]
+            .iter()
+            .map(|name| format!("aoc-{}/{name}", day.number))
+            .find(|path| Path::new(path).is_file())
+            .or_else(|| self.store.get(store::YEAR, day.number, &self.user).map(|path| path.display().to_string()))
+    }
+
+    /// The local input, or else the fetched one.
+    fn find(&self, day: &Day) -> Option<String> {
+        self.local(day).or_else(|| {
+            let fetcher = self.fetcher.as_ref()?;
+            match self.store.get_or_fetch(store::YEAR, day.number, &self.user, fetcher) {
+                Ok(path) => Some(path.display().to_string()),
+                Err(error) => {
+                    eprintln!("Day {}: {error}", day.number);
+                    None
+                }
+            }
+        })
+    }
+}
+
+/// Every day that has a default input, together with it.
+fn days_with_input(inputs: &Inputs) -> Vec<(&'static Day, Source)> {
+    let mut days = Vec::<(&Day, Source)>::new();
+    for day in registry::DAYS {
+        match inputs.find(day) {
+            Some(path) => days.push((day, Source::Path(path))),
+            None => eprintln!("Day {}: no input, skipping", day.number),
+        }
+    }
+    days
+}
+
+/// The registered day and where its input comes from, from `--day`, `--input` and `--input-str`.
+fn day_and_source(inputs: &Inputs, number: u8, input: Option<String>, input_str: Option<String>) -> (&'static Day, Source) {
+    let Some(day) = registry::find(number) else {
+        eprintln!("Day {number} is not registered");
+        exit(1);
+    };
+    let source = match (input, input_str) {
+        (_, Some(text)) => Source::Inline(text),
+        (Some(arg), None) => Source::from_arg(&arg),
+        (None, None) => match inputs.find(day) {
+            Some(path) => Source::Path(path),
+            None => {
+                eprintln!("No input for day {number}, pass one with --input or --input-str, or set AOC_SESSION to fetch it");
+                exit(1);
+            }
+        },
+    };
+    (day, source)
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// Runs the given parts of a day, printing a diagnostic if the input cannot be parsed or a part has no answer.
+fn run_day(day: &Day, parts: &[u8], input: &Input, format: Format) -> Result<(), ()> {
+    let mut unsolved = false;
+    (day.solve)(&input.text, parts, &mut |part, answer, elapsed| match answer {
+        Ok(answer) => println!("{}", Answer::new(day.number, part, &answer, elapsed, &input.name).format(format)),
+        Err(error) => {
+            eprintln!("Day {} part {part}: {error}", day.number);
+            unsolved = true;
+        }
+    })
+    .map_err(|error| eprintln!("{}", error.in_file(&input.name)))?;
+    if unsolved {
+        return Err(());
+    }
+    Ok(())
+}
+
+/// Solves every day that has an input on a pool of threads and prints all answers once they are in.
+fn run_all(inputs: &Inputs, args: &RunArgs) {
+    let jobs = days_with_input(inputs);
+    let outcomes = pool::solve_all(&jobs, &parts(args.part), threads(args.jobs));
+    match args.format {
+        Format::Text => print!("{}", pool::table(&outcomes)),
+        Format::Json => {
+            for outcome in &outcomes {
+                match (&outcome.answer, &outcome.status) {
+                    (Some(answer), _) => println!("{}", Answer::new(outcome.day, outcome.part, answer, outcome.elapsed, &outcome.input_path).format(Format::Json)),
+                    (None, Status::Failed(error)) => eprintln!("Day {} part {}: {error}", outcome.day, outcome.part),
+                    (None, Status::Solved) => {}
+                }
+            }
+        }
+    }
+    if outcomes.iter().any(|outcome| outcome.status != Status::Solved) {
+        exit(1);
+    }
+}
+
+fn run(inputs: &Inputs, args: RunArgs) {
+    if args.all {
+        run_all(inputs, &args);
+        return;
+    }
+    let (day, source) = day_and_source(inputs, args.day.unwrap(), args.input, args.input_str);
+    let input = source.read().unwrap_or_else(|error| {
+        eprintln!("{error}");
+        exit(1);
+    });
+    let parts = parts(args.part);
+    if run_day(day, &parts, &input, args.format).is_err() {
+        exit(1);
+    }
+    if let Some(path) = args.render {
+        if render_day(day, *parts.last().unwrap(), &input, &path).is_err() {
+            exit(1);
+        }
+    }
+}
+
+/// Draws the picture behind a part's answer into the file, in the format its name asks for.
+fn render_day(day: &Day, part: u8, input: &Input, path: &str) -> Result<(), ()> {
+    let Some(render) = day.render else {
+        eprintln!("Day {} has no picture to render", day.number);
+        return Err(());
+    };
+    let picture = render(&input.text, part)
+        .map_err(|error| eprintln!("{}", error.in_file(&input.name)))?
+        .map_err(|error| eprintln!("Day {} part {part}: {error}", day.number))?;
+    let bytes = render::encode(&picture, render::Format::from_path(path));
+    if path == "-" {
+        return io::stdout().write_all(&bytes).map_err(|error| eprintln!("{error}"));
+    }
+    fs::write(path, bytes).map_err(|error| eprintln!("{path}: {error}"))
+}
+
+fn bench_day(day: &Day, source: &Source, runs: usize) -> Result<Benchmark, ()> {
+    let input = source.read().map_err(|error| eprintln!("{error}"))?;
+    bench::measure(day, &input.text, &input.name, runs).map_err(|error| eprintln!("{}", error.in_file(&input.name)))
+}
+
+fn bench(inputs: &Inputs, args: BenchArgs) {
+    let runs = args.runs as usize;
+    let mut benchmarks = Vec::<Benchmark>::new();
+    let mut failed = false;
+    if args.all {
+        for (day, source) in days_with_input(inputs) {
+            match bench_day(day, &source, runs) {
+                Ok(benchmark) => benchmarks.push(benchmark),
+                Err(()) => failed = true,
+            }
+        }
+    } else {
+        let (day, source) = day_and_source(inputs, args.day.unwrap(), args.input, args.input_str);
+        match bench_day(day, &source, runs) {
+            Ok(benchmark) => benchmarks.push(benchmark),
+            Err(()) => failed = true,
+        }
+    }
+    let json = serde_json::to_string_pretty(&benchmarks).unwrap();
+    match args.json.as_deref() {
+        Some("-") => println!("{json}"),
+        Some(path) => {
+            print!("{}", bench::table(&benchmarks));
+            if let Err(error) = fs::write(path, json + "\n") {
+                eprintln!("{path}: {error}");
+                failed = true;
+            }
+        }
+        None => print!("{}", bench::table(&benchmarks)),
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn threads(jobs: Option<u64>) -> usize {
+    match jobs {
+        Some(jobs) => jobs as usize,
+        None => thread::available_parallelism().map_or(1, |threads| threads.get()),
+    }
+}
+
+fn verify(inputs: &Inputs, args: VerifyArgs) {
+    let mut answers = Answers::load(&args.answers).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        exit(1);
+    });
+    let jobs = if args.all { days_with_input(inputs) } else { vec![day_and_source(inputs, args.day.unwrap(), args.input, args.input_str)] };
+
+    let mut checks = Vec::<(Outcome, Verdict)>::new();
+    for outcome in pool::solve_all(&jobs, &[1, 2], threads(args.jobs)) {
+        // an input that cannot be read has already failed its outcome
+        let input_hash = outcome.input_hash.clone().unwrap_or_default();
+        let mut verdict = answers::verdict(&outcome, answers.get(outcome.day, outcome.part, &input_hash));
+        if let (true, Verdict::Unknown, Some(answer)) = (args.record, &verdict, &outcome.answer) {
+            let input_path = outcome.input_path.clone();
+            answers.record(Recorded { day: outcome.day, part: outcome.part, input_hash, input_path, answer: answer.clone() });
+            verdict = Verdict::Recorded;
+        }
+        checks.push((outcome, verdict));
+    }
+    print!("{}", answers::table(&checks));
+
+    let mut failed = checks.iter().any(|(_, verdict)| verdict.is_failure());
+    if checks.iter().any(|(_, verdict)| *verdict == Verdict::Recorded) {
+        if let Err(error) = answers.save(&args.answers) {
+            eprintln!("{error}");
+            failed = true;
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn generate(args: GenerateArgs) {
+    let Some(day) = registry::find(args.day) else {
+        eprintln!("Day {} is not registered", args.day);
+        exit(1);
+    };
+    let input = (day.generate)(args.seed, args.size);
+    match args.output {
+        Some(path) => {
+            if let Err(error) = fs::write(&path, input) {
+                eprintln!("{path}: {error}");
+                exit(1);
+            }
+        }
+        None => print!("{input}"),
+    }
+}
+
+fn differential(args: DifferentialArgs) {
+    let days = registry::DAYS.iter().filter(|day| day.check.is_some() && args.day.is_none_or(|number| number == day.number)).collect::<Vec<_>>();
+    if days.is_empty() {
+        eprintln!("Day {} has no reference solution", args.day.unwrap());
+        exit(1);
+    }
+    let mut failed = false;
+    for day in days {
+        match differential::find_counterexample(day, args.seeds, args.size) {
+            None => println!("Day {}: agrees with its reference on {} inputs", day.number, args.seeds),
+            Some(counterexample) => {
+                let differential::Counterexample { day, seed, size, input, problems } = counterexample;
+                println!("Day {day}: disagrees with its reference on `aoc generate --day {day} --seed {seed} --size {size}`:");
+                for problem in problems {
+                    println!("  {problem}");
+                }
+                print!("{input}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn fuzz(args: FuzzArgs) {
+    // the panics are reported with the input that caused them, and shrinking it causes many more
+    panic::set_hook(Box::new(|_| {}));
+    let mut failed = false;
+    for day in registry::DAYS.iter().filter(|day| args.day.is_none_or(|number| number == day.number)) {
+        match fuzz::find_crash(day, args.runs, args.seed, Duration::from_millis(args.timeout_ms)) {
+            None => println!("Day {}: no panics or hangs on {} inputs", day.number, args.runs),
+            Some(crash) => {
+                match crash.failure {
+                    Failure::Panic(message) => println!("Day {}: panicked: {message}", crash.day),
+                    Failure::Hang => println!("Day {}: still running after {} ms", crash.day, args.timeout_ms),
+                }
+                println!("{:?}", crash.input);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn step(inputs: &Inputs, args: StepArgs) {
+    let (day, source) = day_and_source(inputs, args.day, args.input, args.input_str);
+    let Some(simulate) = day.simulate else {
+        eprintln!("Day {} has no simulation to step through", day.number);
+        exit(1);
+    };
+    let input = source.read().unwrap_or_else(|error| {
+        eprintln!("{error}");
+        exit(1);
+    });
+    let steps = simulate(&input.text).unwrap_or_else(|error| {
+        eprintln!("{}", error.in_file(&input.name));
+        exit(1);
+    });
+    if let Err(error) = stepper::interact(Stepper::new(steps, args.breakpoints)) {
+        eprintln!("{error}");
+        exit(1);
+    }
+}
+
+fn fetch(inputs: &Inputs, args: FetchArgs) {
+    let Some(fetcher) = &inputs.fetcher else {
+        eprintln!("Set AOC_SESSION to the session cookie of the puzzle server to fetch inputs");
+        exit(1);
+    };
+    let days = registry::DAYS.iter().filter(|day| args.all || args.day == Some(day.number));
+    let mut failed = false;
+    for day in days {
+        match inputs.store.get_or_fetch(store::YEAR, day.number, &inputs.user, fetcher) {
+            Ok(path) => println!("Day {:2}  {}", day.number, path.display()),
+            Err(error) => {
+                eprintln!("Day {}: {error}", day.number);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn list(inputs: &Inputs) {
+    for day in registry::DAYS {
+        match inputs.local(day) {
+            Some(path) => println!("Day {:2}  {path}", day.number),
+            None => println!("Day {:2}  (no input)", day.number),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    aoc_common::logging::init(cli.verbose);
+    let inputs = Inputs::new(&cli);
+    match cli.command {
+        Command::Run(args) => run(&inputs, args),
+        Command::Bench(args) => bench(&inputs, args),
+        Command::Verify(args) => verify(&inputs, args),
+        Command::Generate(args) => generate(args),
+        Command::Differential(args) => differential(args),
+        Command::Fuzz(args) => fuzz(args),
+        Command::Step(args) => step(&inputs, args),
+        Command::Fetch(args) => fetch(&inputs, args),
+        Command::List => list(&inputs),
+    }
+}
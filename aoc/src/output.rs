@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How solved parts are printed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// One sentence per part
+    #[default]
+    Text,
+    /// One JSON object per part and line
+    Json,
+}
+
+/// A solved part, as printed with `--format json`.
+#[derive(Serialize)]
+pub struct Answer<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub elapsed_ms: f64,
+    pub input_path: &'a str,
+}
+
+impl<'a> Answer<'a> {
+    pub fn new(day: u8, part: u8, answer: &'a str, elapsed: Duration, input_path: &'a str) -> Self {
+        Answer { day, part, answer, elapsed_ms: elapsed.as_secs_f64() * 1000.0, input_path }
+    }
+
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Text => format!("Day {} part {}: {}", self.day, self.part, self.answer),
+            Format::Json => serde_json::to_string(self).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::Source;
+
+use crate::answers;
+use crate::registry::Day;
+
+/// Some days recurse deeply, the workers get the stack size of a main thread and then some.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Whether a part was solved, and if not, why.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Solved,
+    Failed(String),
+}
+
+/// One part of one day, as run by `solve_all`.
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    /// The file the input was read from, `-` for standard input or `<input-str>`.
+    pub input_path: String,
+    /// Identifies the input for the answers file, if it could be read.
+    pub input_hash: Option<String>,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Reads the input and solves the given parts of a day. A read error, parse error or panic fails every part that
+/// was not solved before it happened.
+pub fn solve_one(day: &Day, parts: &[u8], source: &Source) -> Vec<Outcome> {
+    let mut outcomes = Vec::<Outcome>::new();
+    let mut input_path = source.name().to_string();
+    let error = match source.read() {
+        Err(error) => Some(error),
+        Ok(input) => {
+            input_path = input.name.clone();
+            let input_hash = answers::input_hash(&input.text);
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                (day.solve)(&input.text, parts, &mut |part, answer, elapsed| {
+                    let (input_path, input_hash) = (input.name.clone(), Some(input_hash.clone()));
+                    let (answer, status) = match answer {
+                        Ok(answer) => (Some(answer), Status::Solved),
+                        Err(error) => (None, Status::Failed(error.to_string())),
+                    };
+                    outcomes.push(Outcome { day: day.number, part, input_path, input_hash, answer, elapsed, status });
+                })
+            }));
+            match solved {
+                Ok(Ok(())) => None,
+                Ok(Err(error)) => Some(error.in_file(&input.name).to_string()),
+                Err(payload) => Some(format!("panicked: {}", panic_message(payload.as_ref()))),
+            }
+        }
+    };
+    if let Some(error) = error {
+        let unsolved = parts.iter().filter(|&&part| outcomes.iter().all(|outcome| outcome.part != part)).copied().collect::<Vec<u8>>();
+        for part in unsolved {
+            let status = Status::Failed(error.clone());
+            let input_path = input_path.clone();
+            outcomes.push(Outcome { day: day.number, part, input_path, input_hash: None, answer: None, elapsed: Duration::ZERO, status });
+        }
+    }
+    outcomes
+}
+
+/// Solves every day on its input with `threads` workers, each taking the next unsolved day until none are left.
+/// Days fail in isolation, so a panic in one still lets all others finish. The outcomes are sorted by day and part.
+pub fn solve_all(jobs: &[(&Day, Source)], parts: &[u8], threads: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::<Outcome>::new());
+    thread::scope(|scope| {
+        for worker in 0..threads.clamp(1, jobs.len().max(1)) {
+            thread::Builder::new()
+                .name(format!("worker {worker}"))
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || {
+                    while let Some((day, source)) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let solved = solve_one(day, parts, source);
+                        outcomes.lock().unwrap().extend(solved);
+                    }
+                })
+                .unwrap();
+        }
+    });
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|outcome| (outcome.day, outcome.part));
+    outcomes
+}
+
+pub fn table(outcomes: &[Outcome]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:<20}  {:>10}  {}\n", "Day", "Part", "Answer", "Time ms", "Status");
+    for outcome in outcomes {
+        let status = match &outcome.status {
+            Status::Solved => "ok".to_string(),
+            Status::Failed(error) => format!("failed: {error}"),
+        };
+        table += &format!(
+            "{:>3}  {:>4}  {:<20}  {:>10.3}  {status}\n",
+            outcome.day,
+            outcome.part,
+            outcome.answer.as_deref().unwrap_or("-"),
+            outcome.elapsed.as_secs_f64() * 1000.0
+        );
+    }
+    table
+}
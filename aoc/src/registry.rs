@@ -0,0 +1,130 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Cell, Check, Generator, Grid, Oracle, ParseError, Render, Rng, Simulation, Solution, SolveError};
+
+use crate::stepper::{Steps, Timeline};
+
+/// Receives each part's answer as soon as it is solved, or why it has none, together with how long solving it took.
+pub type Report<'a> = &'a mut dyn FnMut(u8, Result<String, SolveError>, Duration);
+
+/// Parses an input and compares the solution with its reference on it.
+pub type CheckFn = fn(&str) -> Result<Vec<Check>, ParseError>;
+
+/// Parses an input and draws the picture behind a part's answer.
+pub type RenderFn = fn(&str, u8) -> Result<Result<Grid<Cell>, SolveError>, ParseError>;
+
+/// Parses an input into the start of a simulation that can be stepped through.
+pub type SimulateFn = fn(&str) -> Result<Box<dyn Steps>, ParseError>;
+
+/// A registered day. The solution is erased to strings so every day can be run the same way.
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str, &[u8], Report) -> Result<(), ParseError>,
+    pub time: fn(&str) -> Result<Timings, ParseError>,
+    /// Writes a random input from a seed and a size.
+    pub generate: fn(u64, usize) -> String,
+    /// Compares the solution with a slow reference, for the days that have one.
+    pub check: Option<CheckFn>,
+    /// Draws what the solution found over the input, for the days with a picture to show.
+    pub render: Option<RenderFn>,
+    /// Starts the simulation behind the solution, for the days that play out step by step.
+    pub simulate: Option<SimulateFn>,
+}
+
+/// How long each stage of a day took on one input.
+#[derive(Copy, Clone, Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Parses the input once and solves the requested parts.
+fn solve<S: Solution>(input: &str, parts: &[u8], report: Report) -> Result<(), ParseError> {
+    let model = S::parse(input)?;
+    for &part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&model).map(|answer| answer.to_string()),
+            2 => S::part2(&model).map(|answer| answer.to_string()),
+            _ => panic!("There is no part {part}"),
+        };
+        report(part, answer, start.elapsed());
+    }
+    Ok(())
+}
+
+fn time<S: Solution>(input: &str) -> Result<Timings, ParseError> {
+    let start = Instant::now();
+    let model = S::parse(input)?;
+    let parse = start.elapsed();
+    let start = Instant::now();
+    // a part without an answer takes its time all the same
+    let _ = black_box(S::part1(&model));
+    let part1 = start.elapsed();
+    let start = Instant::now();
+    let _ = black_box(S::part2(&model));
+    let part2 = start.elapsed();
+    Ok(Timings { parse, part1, part2 })
+}
+
+fn generate<S: Generator>(seed: u64, size: usize) -> String {
+    S::generate(&mut Rng::new(seed), size)
+}
+
+fn check<S: Oracle>(input: &str) -> Result<Vec<Check>, ParseError> {
+    Ok(S::checks(&S::parse(input)?))
+}
+
+fn render<S: Render>(input: &str, part: u8) -> Result<Result<Grid<Cell>, SolveError>, ParseError> {
+    Ok(S::render(&S::parse(input)?, part))
+}
+
+fn simulate<S: Simulation + 'static>(input: &str) -> Result<Box<dyn Steps>, ParseError> {
+    Ok(Box::new(Timeline::<S>::start(input)?))
+}
+
+/// A day with nothing but a solution and a generator.
+const fn plain<S: Solution + Generator>(number: u8) -> Day {
+    Day { number, solve: solve::<S>, time: time::<S>, generate: generate::<S>, check: None, render: None, simulate: None }
+}
+
+/// Registers a day, with whichever of `check`, `render` and `simulate` it implements the traits for.
+macro_rules! day {
+    ($number:literal, $solution:ty $(, $feature:ident)*) => {
+        Day { $($feature: Some($feature::<$solution>),)* ..plain::<$solution>($number) }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, aoc_1::Day1),
+    day!(2, aoc_2::Day2),
+    day!(3, aoc_3::Day3),
+    day!(4, aoc_4::Day4),
+    day!(5, aoc_5::Day5),
+    day!(6, aoc_6::Day6),
+    day!(7, aoc_7::Day7),
+    day!(8, aoc_8::Day8),
+    day!(9, aoc_9::Day9),
+    day!(10, aoc_10::Day10, render),
+    day!(11, aoc_11::Day11),
+    day!(12, aoc_12::Day12),
+    day!(13, aoc_13::Day13),
+    day!(14, aoc_14::Day14, render, simulate),
+    day!(15, aoc_15::Day15),
+    day!(16, aoc_16::Day16, render, simulate),
+    day!(17, aoc_17::Day17, render),
+    day!(18, aoc_18::Day18, check),
+    day!(19, aoc_19::Day19, check),
+    day!(20, aoc_20::Day20, simulate),
+    day!(21, aoc_21::Day21, check, simulate),
+    day!(22, aoc_22::Day22, simulate),
+    day!(23, aoc_23::Day23, render),
+    day!(24, aoc_24::Day24),
+    day!(25, aoc_25::Day25),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
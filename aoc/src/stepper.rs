@@ -0,0 +1,316 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use aoc_common::render;
+use aoc_common::{Cell, Grid, ParseError, Simulation};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+
+/// How many of the latest states are kept to step back to. Older ones are forgotten, some simulations run for
+/// millions of steps.
+pub const HISTORY: usize = 10_000;
+
+/// How many steps running to a breakpoint takes at most before it gives up and shows where it got to.
+pub const MAX_RUN: usize = 1_000_000;
+
+/// A simulation that can be stepped back and forth, erased so every day can be stepped the same way.
+pub trait Steps {
+    /// Moves a step on. Steps that were taken before are taken from memory. `false` once the simulation is over.
+    fn forward(&mut self) -> bool;
+    /// Moves a step back, `false` at the start or before the oldest state that is remembered.
+    fn back(&mut self) -> bool;
+    /// The number of steps taken to get to the current state.
+    fn position(&self) -> usize;
+    /// What happened during the step to the current state.
+    fn events(&self) -> &[String];
+    fn picture(&self) -> Grid<Cell>;
+    fn details(&self) -> Vec<String>;
+}
+
+/// The states of a simulation so far, with the events of the step to each, the latest `HISTORY` of them.
+pub struct Timeline<S: Simulation> {
+    states: VecDeque<(S::State, Vec<String>)>,
+    /// The position of the oldest state that is kept.
+    first: usize,
+    /// The current state, as an index into `states`.
+    at: usize,
+    /// Whether the last state kept is the last there is.
+    ended: bool,
+}
+
+impl<S: Simulation> Timeline<S> {
+    pub fn start(input: &str) -> Result<Self, ParseError> {
+        Ok(Timeline { states: VecDeque::from([(S::start(input)?, Vec::new())]), first: 0, at: 0, ended: false })
+    }
+}
+
+impl<S: Simulation> Steps for Timeline<S> {
+    fn forward(&mut self) -> bool {
+        if self.at + 1 < self.states.len() {
+            self.at += 1;
+            return true;
+        }
+        if self.ended {
+            return false;
+        }
+        let Some(next) = S::step(&self.states[self.at].0) else {
+            self.ended = true;
+            return false;
+        };
+        self.states.push_back(next);
+        if self.states.len() > HISTORY {
+            self.states.pop_front();
+            self.first += 1;
+        } else {
+            self.at += 1;
+        }
+        true
+    }
+
+    fn back(&mut self) -> bool {
+        if self.at == 0 {
+            return false;
+        }
+        self.at -= 1;
+        true
+    }
+
+    fn position(&self) -> usize {
+        self.first + self.at
+    }
+
+    fn events(&self) -> &[String] {
+        &self.states[self.at].1
+    }
+
+    fn picture(&self) -> Grid<Cell> {
+        S::picture(&self.states[self.at].0)
+    }
+
+    fn details(&self) -> Vec<String> {
+        S::details(&self.states[self.at].0)
+    }
+}
+
+/// The keys the stepper knows, whichever terminal they come from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Key {
+    Char(char),
+    Left,
+    Right,
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Escape,
+}
+
+/// What the stepper shows and what it is in the middle of: a count typed before a command, or a breakpoint being
+/// typed in.
+pub struct Stepper {
+    pub steps: Box<dyn Steps>,
+    /// Stops running at a step with an event that contains any of them.
+    pub breakpoints: Vec<String>,
+    count: String,
+    prompt: Option<String>,
+    message: String,
+    details: bool,
+    /// The first row of the picture on screen.
+    scroll: usize,
+}
+
+const HELP: &str = "→/l step  ←/h back  N→ N steps  c run to a breakpoint  b add one  B clear them  i details  ↑↓ scroll  q quit";
+
+impl Stepper {
+    pub fn new(steps: Box<dyn Steps>, breakpoints: Vec<String>) -> Self {
+        Stepper { steps, breakpoints, count: String::new(), prompt: None, message: HELP.to_string(), details: true, scroll: 0 }
+    }
+
+    /// Handles a key, `false` once the stepper should close.
+    pub fn key(&mut self, key: Key) -> bool {
+        if let Some(prompt) = &mut self.prompt {
+            match key {
+                Key::Char(c) => prompt.push(c),
+                Key::Backspace => {
+                    prompt.pop();
+                }
+                Key::Enter => {
+                    let breakpoint = self.prompt.take().unwrap();
+                    if !breakpoint.is_empty() {
+                        self.message = format!("breaking on {breakpoint:?}");
+                        self.breakpoints.push(breakpoint);
+                    }
+                }
+                Key::Escape => self.prompt = None,
+                _ => {}
+            }
+            return true;
+        }
+        if let Key::Char(digit @ '0'..='9') = key {
+            self.count.push(digit);
+            self.message = format!("{} times", self.count);
+            return true;
+        }
+        let count = std::mem::take(&mut self.count).parse::<usize>().unwrap_or(1).max(1);
+        match key {
+            Key::Right | Key::Char('l' | 'n' | ' ') => self.forward(count),
+            Key::Left | Key::Char('h' | 'p') => self.back(count),
+            Key::Char('c') => self.run(),
+            Key::Char('b') => {
+                self.prompt = Some(String::new());
+            }
+            Key::Char('B') => {
+                self.breakpoints.clear();
+                self.message = "no breakpoints".to_string();
+            }
+            Key::Char('i') => self.details = !self.details,
+            Key::Up | Key::Char('k') => self.scroll = self.scroll.saturating_sub(count),
+            Key::Down | Key::Char('j') => self.scroll += count,
+            Key::Char('q') | Key::Escape => return false,
+            _ => self.message = HELP.to_string(),
+        }
+        true
+    }
+
+    fn forward(&mut self, count: usize) {
+        let taken = (0..count).take_while(|_| self.steps.forward()).count();
+        self.message = if taken < count { "the simulation is over".to_string() } else { String::new() };
+    }
+
+    fn back(&mut self, count: usize) {
+        let taken = (0..count).take_while(|_| self.steps.back()).count();
+        self.message = if taken < count { "no earlier state is remembered".to_string() } else { String::new() };
+    }
+
+    /// Steps on until a step has an event a breakpoint matches, the simulation is over, or `MAX_RUN` steps went by.
+    pub fn run(&mut self) {
+        if self.breakpoints.is_empty() {
+            self.message = "no breakpoints, add one with b".to_string();
+            return;
+        }
+        for _ in 0..MAX_RUN {
+            if !self.steps.forward() {
+                self.message = "the simulation is over".to_string();
+                return;
+            }
+            let hit = self.breakpoints.iter().find(|breakpoint| self.steps.events().iter().any(|event| event.contains(breakpoint.as_str())));
+            if let Some(breakpoint) = hit {
+                self.message = format!("stopped at {breakpoint:?}");
+                return;
+            }
+        }
+        self.message = format!("no breakpoint within {MAX_RUN} steps");
+    }
+
+    /// The screen as text coloured for a terminal, at most `width` columns and `height` lines: the picture, and
+    /// below it the step, its events, the details and the status line.
+    pub fn screen(&self, width: usize, height: usize) -> String {
+        let mut footer = vec![format!("step {}", self.steps.position())];
+        footer.extend(self.steps.events().iter().take(5).map(|event| format!("  {event}")));
+        if self.steps.events().len() > 5 {
+            footer.push(format!("  and {} more", self.steps.events().len() - 5));
+        }
+        if self.details {
+            footer.extend(self.steps.details());
+        }
+        if !self.breakpoints.is_empty() {
+            footer.push(format!("breakpoints: {}", self.breakpoints.iter().map(|breakpoint| format!("{breakpoint:?}")).collect::<Vec<_>>().join(", ")));
+        }
+        footer.push(match &self.prompt {
+            Some(prompt) => format!("break on events containing: {prompt}"),
+            None => self.message.clone(),
+        });
+        let footer = footer.into_iter().map(|line| line.chars().take(width).collect::<String>()).collect::<Vec<_>>();
+
+        let picture = self.steps.picture();
+        let rows = height.saturating_sub(footer.len()).min(picture.height().saturating_sub(self.scroll));
+        let columns = width.min(picture.width());
+        let cells = (self.scroll..self.scroll + rows).flat_map(|y| picture.row(y)[..columns].iter().copied()).collect();
+        let mut screen = render::ansi(&Grid::new(columns, rows, cells));
+        for line in footer {
+            screen += &line;
+            screen.push('\n');
+        }
+        screen
+    }
+}
+
+fn key(event: KeyEvent) -> Option<Key> {
+    if event.kind == KeyEventKind::Release {
+        return None;
+    }
+    match event.code {
+        KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Escape),
+        KeyCode::Char(c) => Some(Key::Char(c)),
+        KeyCode::Left => Some(Key::Left),
+        KeyCode::Right => Some(Key::Right),
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::Backspace => Some(Key::Backspace),
+        KeyCode::Esc => Some(Key::Escape),
+        _ => None,
+    }
+}
+
+/// The terminal in raw mode on its alternate screen, until this is dropped. Dropping it puts the terminal back however
+/// the stepper ends, with an error or a panic.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        // from here on the terminal is put back, even if switching screens fails
+        let screen = Screen;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // there is nothing left to report a failure to
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shows the stepper on the terminal's alternate screen until it is closed.
+pub fn interact(mut stepper: Stepper) -> io::Result<()> {
+    let _screen = Screen::enter()?;
+    let mut stdout = io::stdout();
+    loop {
+        // a terminal that does not know its size says it has none
+        let (width, height) = terminal::size().ok().filter(|&(width, height)| width > 0 && height > 0).unwrap_or((80, 24));
+        let screen = stepper.screen(width as usize, height as usize);
+        execute!(stdout, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All))?;
+        // raw mode does not go back to the start of the line by itself
+        stdout.write_all(screen.replace('\n', "\r\n").as_bytes())?;
+        stdout.flush()?;
+        loop {
+            match event::read()? {
+                Event::Key(event) => {
+                    if let Some(key) = key(event) {
+                        if !stepper.key(key) {
+                            return Ok(());
+                        }
+                        break;
+                    }
+                }
+                Event::Resize(..) => break,
+                _ => {}
+            }
+        }
+        // let held keys pile up into one redraw
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(event) = event::read()? {
+                if let Some(key) = key(event) {
+                    if !stepper.key(key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The puzzles of this repository are all from this year.
+pub const YEAR: u16 = 2023;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The least time between two requests to the puzzle server, which asks to be spared bursts.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "advent-of-code-2023 input store (ureq)";
+
+/// Gets a puzzle input from somewhere other than the store.
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String>;
+}
+
+/// Downloads inputs from the puzzle server, or anything that answers `GET <base>/<year>/day/<day>/input` like it, as
+/// the user whose session cookie it was given. Requests wait until `interval` has passed since the previous one.
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: &str, session: &str, interval: Duration) -> Self {
+        HttpFetcher { base_url: base_url.trim_end_matches('/').to_string(), session: session.trim().to_string(), interval, last_request: Mutex::new(None) }
+    }
+
+    fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+        if let Some(last_request) = *last_request {
+            let next_request = last_request + self.interval;
+            let now = Instant::now();
+            if next_request > now {
+                log::info!("Waiting {:?} before the next request", next_request - now);
+                thread::sleep(next_request - now);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        self.wait_for_turn();
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        log::info!("Fetching {url}");
+        let response = ureq::get(&url).set("Cookie", &format!("session={}", self.session)).set("User-Agent", USER_AGENT).call();
+        match response {
+            Ok(response) => response.into_string().map_err(|error| format!("{url}: {error}")),
+            Err(ureq::Error::Status(status, response)) => Err(format!("{url}: {status} {}", response.status_text())),
+            Err(error) => Err(format!("{url}: {error}")),
+        }
+    }
+}
+
+/// Puzzle inputs kept on disk as `<root>/<year>/<day>/<user>.txt`, since every user gets their own inputs. An input
+/// that is in the store is never fetched again.
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Store { root: root.into() }
+    }
+
+    /// Where the input is kept, whether it is there yet or not.
+    pub fn path(&self, year: u16, day: u8, user: &str) -> Result<PathBuf, String> {
+        let valid = !user.is_empty() && !user.starts_with('.') && user.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c));
+        if !valid {
+            return Err(format!("`{user}` is not a user name, use letters, digits, `.`, `_` and `-`"));
+        }
+        Ok(self.root.join(year.to_string()).join(format!("{day:02}")).join(format!("{user}.txt")))
+    }
+
+    /// The stored input, if there is one.
+    pub fn get(&self, year: u16, day: u8, user: &str) -> Option<PathBuf> {
+        self.path(year, day, user).ok().filter(|path| path.is_file())
+    }
+
+    /// Stores an input. It is written next to its place first, so a store never holds half an input.
+    pub fn put(&self, year: u16, day: u8, user: &str, input: &str) -> Result<PathBuf, String> {
+        let path = self.path(year, day, user)?;
+        let partial = path.with_extension("partial");
+        fs::create_dir_all(path.parent().unwrap()).map_err(|error| format!("{}: {error}", self.root.display()))?;
+        fs::write(&partial, input).map_err(|error| format!("{}: {error}", partial.display()))?;
+        fs::rename(&partial, &path).map_err(|error| format!("{}: {error}", path.display()))?;
+        Ok(path)
+    }
+
+    /// The stored input, fetched and stored first if it is not there yet.
+    pub fn get_or_fetch(&self, year: u16, day: u8, user: &str, fetcher: &dyn Fetcher) -> Result<PathBuf, String> {
+        if let Some(path) = self.get(year, day, user) {
+            return Ok(path);
+        }
+        let input = fetcher.fetch(year, day)?;
+        if input.trim().is_empty() {
+            return Err(format!("day {day} of {year}: the fetched input is empty"));
+        }
+        self.put(year, day, user, &input)
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use std::fs;
+use std::process::Output;
+use std::time::Duration;
+
+use aoc::answers::{self, Answers, Recorded, Verdict};
+use aoc::pool::{Outcome, Status};
+use common::{aoc, empty_dir, example};
+
+fn recorded(day: u8, part: u8, input_hash: &str, answer: &str) -> Recorded {
+    Recorded { day, part, input_hash: input_hash.to_string(), input_path: format!("aoc-{day}/input"), answer: answer.to_string() }
+}
+
+fn outcome(status: Status, answer: Option<&str>) -> Outcome {
+    Outcome {
+        day: 1,
+        part: 1,
+        input_path: "aoc-1/input".to_string(),
+        input_hash: Some(answers::input_hash("1abc2\n")),
+        answer: answer.map(str::to_string),
+        elapsed: Duration::ZERO,
+        status,
+    }
+}
+
+#[test]
+fn inputs_are_told_apart_by_their_content() {
+    assert_eq!(answers::input_hash(""), "cbf29ce484222325");
+    assert_eq!(answers::input_hash("a"), "af63dc4c8601ec8c");
+    assert_eq!(answers::input_hash(&example(2)), answers::input_hash(&example(2)));
+    assert_ne!(answers::input_hash(&example(2)), answers::input_hash(&example(2).replace('3', "4")));
+}
+
+#[test]
+fn answers_are_kept_per_day_part_and_input() {
+    let mut answers = Answers::default();
+    answers.record(recorded(2, 1, "aa", "8"));
+    answers.record(recorded(2, 2, "aa", "2286"));
+    answers.record(recorded(2, 1, "bb", "9"));
+    answers.record(recorded(2, 1, "aa", "10"));
+    assert_eq!(answers.get(2, 1, "aa"), Some("10"));
+    assert_eq!(answers.get(2, 2, "aa"), Some("2286"));
+    assert_eq!(answers.get(2, 1, "bb"), Some("9"));
+    assert_eq!(answers.get(2, 2, "bb"), None);
+    assert_eq!(answers.get(3, 1, "aa"), None);
+
+    let dir = empty_dir("answers-round-trip");
+    let path = dir.join("answers.toml").display().to_string();
+    assert_eq!(Answers::load(&path).unwrap().get(2, 1, "aa"), None);
+    answers.save(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().starts_with("[[answer]]\nday = 2\npart = 1\n"));
+    let loaded = Answers::load(&path).unwrap();
+    for (part, hash, answer) in [(1, "aa", Some("10")), (2, "aa", Some("2286")), (1, "bb", Some("9")), (2, "bb", None)] {
+        assert_eq!(loaded.get(2, part, hash), answer);
+    }
+
+    fs::write(&path, "[[answer]]\nday = \"two\"\n").unwrap();
+    assert!(Answers::load(&path).unwrap_err().starts_with(&format!("{path}: ")));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn answers_are_judged_against_the_recorded_ones() {
+    assert_eq!(answers::verdict(&outcome(Status::Solved, Some("8")), Some("8")), Verdict::Correct);
+    assert_eq!(answers::verdict(&outcome(Status::Solved, Some("9")), Some("8")), Verdict::Wrong { expected: "8".to_string() });
+    assert_eq!(answers::verdict(&outcome(Status::Solved, Some("8")), None), Verdict::Unknown);
+    let failed = outcome(Status::Failed("no answer: nothing".to_string()), None);
+    assert_eq!(answers::verdict(&failed, Some("8")), Verdict::Failed("no answer: nothing".to_string()));
+    assert_eq!(answers::verdict(&failed, None), Verdict::Failed("no answer: nothing".to_string()));
+
+    assert!(Verdict::Wrong { expected: "8".to_string() }.is_failure());
+    assert!(Verdict::Failed("panicked: boom".to_string()).is_failure());
+    assert!(![Verdict::Correct, Verdict::Unknown, Verdict::Recorded].iter().any(Verdict::is_failure));
+}
+
+#[test]
+fn verify_records_then_checks_the_answers() {
+    let dir = empty_dir("answers-verify");
+    fs::write(dir.join("games"), example(2)).unwrap();
+    let verify = |record: bool| {
+        let mut args = vec!["verify", "--day", "2", "--input", "games", "--answers", "answers.toml"];
+        if record {
+            args.push("--record");
+        }
+        aoc(&dir, &args)
+    };
+    let stdout = |output: &Output| String::from_utf8(output.stdout.clone()).unwrap();
+
+    let unrecorded = verify(false);
+    assert!(unrecorded.status.success());
+    assert_eq!(stdout(&unrecorded).lines().filter(|line| line.ends_with("no recorded answer")).count(), 2);
+    assert!(!dir.join("answers.toml").exists());
+
+    let recording = verify(true);
+    assert!(recording.status.success());
+    assert_eq!(stdout(&recording), "Day  Part  Answer                Status\n  2     1  8                     recorded\n  2     2  2286                  recorded\n");
+    let answers = Answers::load(&dir.join("answers.toml").display().to_string()).unwrap();
+    let hash = answers::input_hash(&example(2));
+    assert_eq!((answers.get(2, 1, &hash), answers.get(2, 2, &hash)), (Some("8"), Some("2286")));
+
+    let passing = verify(true);
+    assert!(passing.status.success());
+    assert_eq!(stdout(&passing), "Day  Part  Answer                Status\n  2     1  8                     ok\n  2     2  2286                  ok\n");
+
+    let edited = fs::read_to_string(dir.join("answers.toml")).unwrap().replace("\"2286\"", "\"2287\"");
+    fs::write(dir.join("answers.toml"), edited).unwrap();
+    let mismatch = verify(true);
+    assert_eq!(mismatch.status.code(), Some(1));
+    assert!(stdout(&mismatch).contains("  2     1  8                     ok\n  2     2  2286                  WRONG, expected 2287\n"));
+
+    // the recorded answers belong to the old input, so the new one has none yet
+    fs::write(dir.join("games"), example(2).lines().take(1).collect::<String>() + "\n").unwrap();
+    let changed = verify(false);
+    assert!(changed.status.success());
+    assert_eq!(stdout(&changed), "Day  Part  Answer                Status\n  2     1  1                     no recorded answer\n  2     2  48                    no recorded answer\n");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use aoc::bench;
+use aoc::registry::{self, Day, Timings};
+use aoc_common::{ParseError, Position};
+use common::{aoc, empty_dir, example};
+
+static RUNS: AtomicU64 = AtomicU64::new(0);
+
+/// Takes 5, 1, 4, 2 and then 3 ms to parse, each part twice and three times as long.
+fn scripted(_input: &str) -> Result<Timings, ParseError> {
+    let ms = [5, 1, 4, 2, 3][RUNS.fetch_add(1, Ordering::SeqCst) as usize % 5];
+    Ok(Timings { parse: Duration::from_millis(ms), part1: Duration::from_millis(2 * ms), part2: Duration::from_millis(3 * ms) })
+}
+
+fn unparsable(input: &str) -> Result<Timings, ParseError> {
+    Err(ParseError::new(Position::start_of(1), input, "not a number"))
+}
+
+#[test]
+fn benchmarks_report_the_median_run() {
+    let day = Day { time: scripted, ..*registry::find(2).unwrap() };
+    let benchmark = bench::measure(&day, "", "aoc-2/input", 5).unwrap();
+    assert_eq!((benchmark.day, benchmark.input_path.as_str(), benchmark.runs), (2, "aoc-2/input", 5));
+    assert_eq!((benchmark.parse_ms, benchmark.part1_ms, benchmark.part2_ms, benchmark.total_ms()), (3.0, 6.0, 9.0, 18.0));
+
+    let json = serde_json::to_value(&benchmark).unwrap();
+    assert_eq!(json, serde_json::json!({
+        "day": 2, "input_path": "aoc-2/input", "runs": 5, "parse_ms": 3.0, "part1_ms": 6.0, "part2_ms": 9.0,
+    }));
+    assert_eq!(bench::table(&[benchmark]).lines().nth(1), Some("  2  aoc-2/input                    3.000       6.000       9.000      18.000"));
+}
+
+#[test]
+fn benchmarks_run_at_least_once_and_stop_at_a_parse_error() {
+    let day = registry::find(2).unwrap();
+    assert_eq!(bench::measure(day, &example(2), "test", 0).unwrap().runs, 1);
+    let broken = Day { time: unparsable, ..*day };
+    assert_eq!(bench::measure(&broken, "x", "test", 3).err().unwrap().to_string(), "1:1: not a number: `x`");
+}
+
+#[test]
+fn bench_prints_json() {
+    let dir = empty_dir("bench");
+    fs::write(dir.join("games"), example(2)).unwrap();
+    let output = aoc(&dir, &["bench", "--day", "2", "--input", "games", "--runs", "3", "--json", "-"]);
+    fs::remove_dir_all(dir).unwrap();
+    assert!(output.status.success());
+
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+    let [benchmark] = json.as_array().unwrap().as_slice() else { panic!("{json}") };
+    let keys = benchmark.as_object().unwrap().keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(keys, ["day", "input_path", "parse_ms", "part1_ms", "part2_ms", "runs"]);
+    assert_eq!((&benchmark["day"], &benchmark["input_path"], &benchmark["runs"]), (&2.into(), &"games".into(), &3.into()));
+    for stage in ["parse_ms", "part1_ms", "part2_ms"] {
+        assert!(benchmark[stage].as_f64().unwrap() >= 0.0, "{stage}");
+    }
+}
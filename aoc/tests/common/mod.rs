@@ -0,0 +1,33 @@
+//! Helpers shared by the integration tests, each of which uses only some of them.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+/// A file of the workspace, by its path from the workspace root.
+pub fn workspace_file(path: &str) -> String {
+    fs::read_to_string(workspace_root().join(path)).unwrap_or_else(|error| panic!("{path}: {error}"))
+}
+
+/// The example input next to a day's crate.
+pub fn example(day: u8) -> String {
+    workspace_file(&format!("aoc-{day}/test"))
+}
+
+/// A directory of the test's own under the temporary directory, emptied of what an earlier run left in it.
+pub fn empty_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the binary in `dir`, without a session, so it never fetches anything.
+pub fn aoc(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc")).args(args).current_dir(dir).env_remove("AOC_SESSION").output().unwrap()
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use aoc::registry;
+use aoc_common::normalize;
+use common::workspace_file;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Manifest {
+    example: Vec<Example>,
+}
+
+#[derive(Deserialize)]
+struct Example {
+    day: u8,
+    input: String,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+fn manifest() -> Manifest {
+    toml::from_str(&workspace_file("examples.toml")).unwrap_or_else(|error| panic!("examples.toml: {error}"))
+}
+
+/// Solves every example after passing its input through `edit`, and describes every answer that is not the expected one.
+fn wrong_answers(edit: impl Fn(&str) -> String) -> Vec<String> {
+    let mut failures = Vec::<String>::new();
+    for example in manifest().example {
+        let day = registry::find(example.day).unwrap_or_else(|| panic!("day {} is not registered", example.day));
+        let input = edit(&workspace_file(&example.input));
+        let expected = [(1, example.part1), (2, example.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| answer.map(|answer| (part, answer)))
+            .collect::<Vec<(u8, String)>>();
+        let parts = expected.iter().map(|(part, _)| *part).collect::<Vec<u8>>();
+        let mut answers = Vec::<(u8, String)>::new();
+        if let Err(error) = (day.solve)(&input, &parts, &mut |part, answer, _| answers.push((part, answer.unwrap_or_else(|error| error.to_string())))) {
+            failures.push(error.in_file(&example.input).to_string());
+            continue;
+        }
+        for ((part, expected), (_, answer)) in expected.iter().zip(&answers) {
+            if answer != expected {
+                failures.push(format!("{} part {part}: expected {expected}, got {answer}", example.input));
+            }
+        }
+    }
+    failures
+}
+
+#[test]
+fn examples_give_expected_answers() {
+    let failures = wrong_answers(str::to_string);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn examples_saved_with_crlf_and_trailing_whitespace_give_expected_answers() {
+    let failures = wrong_answers(|input| normalize(&(input.replace('\n', " \t\r\n") + "\r\n  \r\n")));
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_day_has_an_example() {
+    let manifest = manifest();
+    for day in registry::DAYS {
+        assert!(manifest.example.iter().any(|example| example.day == day.number), "day {} has no example", day.number);
+    }
+}
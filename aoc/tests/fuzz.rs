@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use aoc::fuzz::{self, Failure};
+use aoc::registry;
+
+/// Generous for a debug build, the point is that nothing runs forever.
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+#[test]
+fn mutated_inputs_neither_panic_nor_hang() {
+    let crashes = registry::DAYS.iter()
+        .filter_map(|day| fuzz::find_crash(day, 20, 0, TIMEOUT))
+        .map(|crash| format!("day {}: {:?} on {:?}", crash.day, crash.failure, crash.input))
+        .collect::<Vec<_>>();
+    assert!(crashes.is_empty(), "{}", crashes.join("\n"));
+}
+
+/// Inputs that used to panic or hang.
+#[test]
+fn former_crashes_are_errors() {
+    let inputs = [
+        (1, "٣two\n"),
+        (6, "Time: 4294967296\nDistance: 1\n"),
+        (7, "AAAAA 18446744073709551615\nKKKKK 18446744073709551615\n"),
+        (8, "é\n\nAAA = (AAA, AAA)\n"),
+        (8, "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n"),
+        (9, "1 2 4 8 16\n"),
+        (10, "S.\n..\n"),
+        (12, "??????????????????????????????????? 1\n"),
+        (18, "R 6 (#70c710)\nD 5 (#0dc571)\n"),
+        (19, "in{x>10:in,A}\n\n{x=787,m=2655,a=1222,s=2876}\n"),
+        (20, "broadcaster -> a\n%a -> a\n"),
+        (22, "1,0,0~1,2,0\n"),
+        (25, "a: b\n"),
+    ];
+    for (number, input) in inputs {
+        let day = registry::find(number).unwrap();
+        assert_eq!(fuzz::failure(day, input, TIMEOUT), None::<Failure>, "day {number} on {input:?}");
+    }
+}
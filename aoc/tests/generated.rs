@@ -0,0 +1,50 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc::{differential, registry};
+
+/// Small enough for every day to solve quickly in a debug build.
+const SIZE: usize = 3;
+const SEEDS: u64 = 5;
+
+#[test]
+fn generated_inputs_are_solved() {
+    let mut failures = Vec::<String>::new();
+    for day in registry::DAYS {
+        for seed in 0..SEEDS {
+            let input = (day.generate)(seed, SIZE);
+            let mut unsolved = Vec::<String>::new();
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                (day.solve)(&input, &[1, 2], &mut |part, answer, _| {
+                    if let Err(error) = answer {
+                        unsolved.push(format!("day {} seed {seed} part {part}: {error}\n{input}", day.number));
+                    }
+                })
+            }));
+            failures.append(&mut unsolved);
+            match solved {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => failures.push(format!("day {} seed {seed}: {error}\n{input}", day.number)),
+                Err(_) => failures.push(format!("day {} seed {seed}: panicked\n{input}", day.number)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn generated_inputs_depend_only_on_seed_and_size() {
+    // large enough for every day to have some room for randomness
+    for day in registry::DAYS {
+        assert_eq!((day.generate)(7, 10), (day.generate)(7, 10), "day {}", day.number);
+        assert_ne!((day.generate)(7, 10), (day.generate)(8, 10), "day {}", day.number);
+    }
+}
+
+#[test]
+fn solutions_agree_with_their_references() {
+    for day in registry::DAYS {
+        if let Some(counterexample) = differential::find_counterexample(day, 10, 5) {
+            panic!("day {} seed {} size {}: {}\n{}", day.number, counterexample.seed, counterexample.size, counterexample.problems.join("\n"), counterexample.input);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use aoc_common::{Direction, Position, Solution};
+use common::example;
+
+#[test]
+fn days_can_be_used_as_libraries() {
+    let records = aoc_12::parse(&example(12)).unwrap();
+    let arrangements = records.iter().map(|(states, groups)| aoc_12::arrangements(states, groups)).collect::<Vec<_>>();
+    assert_eq!(arrangements, [1, 4, 1, 1, 4, 10].map(Some));
+    assert_eq!(records.iter().map(|(states, groups)| aoc_12::unfolded_arrangements(states, groups)).sum::<Option<usize>>(), Some(525152));
+
+    let contraption = aoc_16::parse(&example(16)).unwrap();
+    assert_eq!(aoc_16::get_energized_tiles(&contraption, (3, 0, Direction::South)), 51);
+    assert_eq!(aoc_16::most_energized_tiles(&contraption), 51);
+
+    let map = aoc_23::parse(&example(23)).unwrap();
+    let junctions = aoc_23::junction_graph(&map);
+    assert_eq!(aoc_23::longest_path(&aoc_23::downhill_junction_graph(&map)), Some(94));
+    assert_eq!(aoc_23::longest_path(&junctions), Some(154));
+}
+
+#[test]
+fn almanac_errors_point_at_the_number() {
+    let Err(error) = aoc_5::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2305843009213693953\n") else {
+        panic!("a number above 2^60 parses");
+    };
+    assert_eq!((error.position, error.text.as_str()), (Position::new(4, 7), "2305843009213693953"));
+
+    let almanac = aoc_5::parse("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n").unwrap();
+    assert_eq!(aoc_5::Day5::part1(&almanac).unwrap(), 14);
+    assert!(aoc_5::Day5::part2(&almanac).unwrap_err().to_string().contains("do not pair up"));
+}
+
+#[test]
+fn almanacs_without_seeds_have_no_lowest_location() {
+    let almanac = aoc_5::parse("seeds: 79 0\n\nseed-to-soil map:\n50 98 2\n").unwrap();
+    assert_eq!((aoc_5::lowest_location(&almanac), aoc_5::lowest_range_location(&almanac)), (Some(0), None));
+    assert!(aoc_5::Day5::part2(&almanac).is_err());
+    let almanac = aoc_5::parse("seeds:\n").unwrap();
+    assert_eq!(aoc_5::Day5::part1(&almanac).unwrap_err().to_string(), "no answer: there are no seeds");
+}
+
+#[test]
+fn dig_plans_only_go_up_right_down_or_left() {
+    for direction in ["N", "E", "S", "W", "X"] {
+        let error = aoc_18::parse(&format!("R 6 (#70c710)\n{direction} 6 (#0dc571)\n")).unwrap_err();
+        assert_eq!((error.position, error.text.as_str()), (Position::new(2, 1), direction));
+        assert_eq!(error.message, "expected U, R, D or L");
+    }
+    let plan = aoc_18::parse("R 6 (#70c710)\nD 5 (#0dc571)\nL 2 (#5713f0)\nU 2 (#d2c081)\n").unwrap();
+    let directions = plan.iter().map(|instruction| instruction.direction).collect::<Vec<_>>();
+    assert_eq!(directions, [Direction::East, Direction::South, Direction::West, Direction::North]);
+}
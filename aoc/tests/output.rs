@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+use std::process::Output;
+use std::time::Duration;
+
+use aoc::output::{Answer, Format};
+use common::{aoc, empty_dir, example, workspace_file, workspace_root};
+use serde_json::Value;
+
+fn json_lines(output: &Output) -> Vec<Value> {
+    String::from_utf8(output.stdout.clone()).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn answers_print_as_text_or_json() {
+    let answer = Answer::new(5, 2, "46", Duration::from_micros(1500), "aoc-5/input");
+    assert_eq!(answer.format(Format::Text), "Day 5 part 2: 46");
+    assert_eq!(answer.format(Format::Json), r#"{"day":5,"part":2,"answer":"46","elapsed_ms":1.5,"input_path":"aoc-5/input"}"#);
+}
+
+#[test]
+fn run_prints_one_json_object_per_part() {
+    let dir = empty_dir("output");
+    fs::write(dir.join("games"), example(2)).unwrap();
+    let output = aoc(&dir, &["run", "--day", "2", "--input", "games", "--format", "json"]);
+    fs::remove_dir_all(dir).unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+
+    let lines = json_lines(&output);
+    assert_eq!(lines.len(), 2);
+    for (line, (part, answer)) in lines.iter().zip([(1, "8"), (2, "2286")]) {
+        let keys = line.as_object().unwrap().keys().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(keys, ["answer", "day", "elapsed_ms", "input_path", "part"]);
+        assert_eq!((&line["day"], &line["part"], &line["answer"], &line["input_path"]), (&2.into(), &part.into(), &answer.into(), &"games".into()));
+        assert!(line["elapsed_ms"].as_f64().unwrap() >= 0.0);
+    }
+}
+
+#[test]
+fn failed_parts_are_reported_on_stderr_only() {
+    // three seeds are fine for part 1, but do not pair up into the ranges of part 2
+    let almanac = workspace_file("aoc-5/example").replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+    let output = aoc(&workspace_root(), &["run", "--day", "5", "--input-str", &almanac, "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let lines = json_lines(&output);
+    assert_eq!(lines.len(), 1);
+    assert_eq!((&lines[0]["part"], &lines[0]["answer"], &lines[0]["input_path"]), (&1.into(), &"43".into(), &"<input-str>".into()));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "Day 5 part 2: no answer: the 3 seed numbers do not pair up into ranges\n");
+
+    let output = aoc(&workspace_root(), &["run", "--day", "5", "--input-str", "seeds: 99999999999999999999\n", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "<input-str>:1:8: invalid u64: `99999999999999999999`\n");
+}
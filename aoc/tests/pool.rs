@@ -0,0 +1,48 @@
+mod common;
+
+use aoc::pool::{self, Status};
+use aoc::registry::{self, Day, Report};
+use aoc_common::{ParseError, Source};
+use common::example;
+
+/// Solves part 1 and then panics in part 2.
+fn panics_in_part_2(_input: &str, parts: &[u8], report: Report) -> Result<(), ParseError> {
+    for &part in parts {
+        assert_ne!(part, 2, "part 2 is broken");
+        report(part, Ok("42".to_string()), Default::default());
+    }
+    Ok(())
+}
+
+#[test]
+fn a_day_that_panics_fails_alone() {
+    let broken = Day { number: 26, solve: panics_in_part_2, ..*registry::find(2).unwrap() };
+    let jobs = [
+        (registry::find(2).unwrap(), Source::Inline(example(2))),
+        (&broken, Source::Inline(String::new())),
+        (registry::find(4).unwrap(), Source::Inline(example(4))),
+    ];
+    for threads in [1, 3] {
+        let outcomes = pool::solve_all(&jobs, &[1, 2], threads);
+        let summary = outcomes.iter().map(|outcome| (outcome.day, outcome.part, outcome.answer.as_deref(), outcome.status.clone())).collect::<Vec<_>>();
+        assert_eq!(summary, [
+            (2, 1, Some("8"), Status::Solved),
+            (2, 2, Some("2286"), Status::Solved),
+            (4, 1, Some("13"), Status::Solved),
+            (4, 2, Some("30"), Status::Solved),
+            (26, 1, Some("42"), Status::Solved),
+            (26, 2, None, Status::Failed("panicked: assertion `left != right` failed: part 2 is broken\n  left: 2\n right: 2".to_string())),
+        ], "{threads} threads");
+        assert!(pool::table(&outcomes).contains("failed: panicked: assertion"));
+    }
+}
+
+#[test]
+fn a_day_that_does_not_parse_fails_every_part() {
+    let outcomes = pool::solve_one(registry::find(2).unwrap(), &[1, 2], &Source::Inline("Game one\n".to_string()));
+    assert_eq!(outcomes.len(), 2);
+    for outcome in outcomes {
+        let Status::Failed(error) = outcome.status else { panic!("part {} was solved", outcome.part) };
+        assert!(error.starts_with("<input-str>:1:1: "), "{error}");
+    }
+}
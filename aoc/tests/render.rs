@@ -0,0 +1,58 @@
+mod common;
+
+use aoc::registry;
+use aoc_common::render::{self, Format};
+use aoc_common::{Cell, Color, Render};
+use common::example;
+
+#[test]
+fn grid_days_render_both_parts_over_their_input() {
+    for day in registry::DAYS.iter().filter(|day| day.render.is_some()) {
+        let input = example(day.number);
+        for part in [1, 2] {
+            let picture = (day.render.unwrap())(&input, part).unwrap().unwrap();
+            assert_eq!((picture.width(), picture.height()), (input.lines().next().unwrap().chars().count(), input.lines().count()), "day {} part {part}", day.number);
+            let highlighted = picture.iter().any(|(_, cell)| cell.background != Color::DARK || !matches!(cell.foreground, Color::GREY | Color::WHITE));
+            assert!(highlighted, "day {} part {part} draws nothing over the input", day.number);
+        }
+    }
+}
+
+#[test]
+fn pictures_are_written_in_every_format() {
+    let picture = aoc_17::Day17::render(&aoc_17::parse(&example(17)).unwrap(), 1).unwrap();
+    assert!(render::encode(&picture, Format::Png).starts_with(b"\x89PNG\r\n\x1a\n"));
+    let svg = String::from_utf8(render::encode(&picture, Format::Svg)).unwrap();
+    assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect").count(), picture.width() * picture.height());
+    let ansi = String::from_utf8(render::encode(&picture, Format::Ansi)).unwrap();
+    assert_eq!(ansi.lines().count(), picture.height());
+    assert!(ansi.lines().all(|line| line.ends_with("\x1b[0m")));
+    assert_eq!(Format::from_path("out.PNG"), Format::Png);
+    assert_eq!(Format::from_path("out.svg"), Format::Svg);
+    assert_eq!(Format::from_path("-"), Format::Ansi);
+}
+
+#[test]
+fn crucible_route_loses_the_answer_in_heat() {
+    let field = aoc_17::parse(&example(17)).unwrap();
+    let (cost, route) = aoc_17::best_route(&field, 1, 3).unwrap();
+    assert_eq!(cost, 102);
+    assert_eq!((route[0], *route.last().unwrap()), ((0, 0), (field.width() - 1, field.height() - 1)));
+    assert_eq!(route[1..].iter().map(|block| field[*block]).sum::<usize>(), cost);
+    let picture = aoc_17::Day17::render(&field, 1).unwrap();
+    assert_eq!(picture.iter().filter(|(_, cell)| cell.background == Color::YELLOW).count(), route.len());
+    assert_eq!(picture[(0, 0)], Cell::new('2', Color::BLACK).on(Color::YELLOW));
+}
+
+#[test]
+fn longest_hike_is_as_long_as_the_answer() {
+    let map = aoc_23::parse(&example(23)).unwrap();
+    for (part, junctions, length) in [(1, aoc_23::downhill_junction_graph(&map), 94), (2, aoc_23::junction_graph(&map), 154)] {
+        let hike = aoc_23::longest_hike(&junctions).unwrap();
+        assert_eq!((hike[0], *hike.last().unwrap()), (0, junctions.len() - 1));
+        let picture = aoc_23::Day23::render(&map, part).unwrap();
+        // the hike's tiles and the tile it starts on
+        assert_eq!(picture.iter().filter(|(_, cell)| cell.background == Color::YELLOW).count(), length + 1, "part {part}");
+    }
+}
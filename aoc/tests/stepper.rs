@@ -0,0 +1,82 @@
+mod common;
+
+use aoc::registry;
+use aoc::stepper::{Key, Stepper};
+use aoc_common::Simulation;
+use common::example;
+
+/// The state after `steps` steps, and the events of each of them.
+fn run<S: Simulation>(input: &str, steps: usize) -> (S::State, Vec<Vec<String>>) {
+    let mut state = S::start(input).unwrap();
+    let mut events = Vec::<Vec<String>>::new();
+    for _ in 0..steps {
+        let (next, step_events) = S::step(&state).unwrap();
+        state = next;
+        events.push(step_events);
+    }
+    (state, events)
+}
+
+#[test]
+fn simulations_step_through_what_the_solutions_compute() {
+    let field = aoc_14::parse(&example(14)).unwrap();
+    let (tilting, events) = run::<aoc_14::Day14>(&example(14), 8);
+    assert_eq!(tilting.field, aoc_14::spin_cycle(&aoc_14::spin_cycle(&field)));
+    assert_eq!(events[3], ["tilt east", "spin cycle 1 ends with a load of 87"]);
+
+    let mut lighting = aoc_16::Day16::start(&example(16)).unwrap();
+    while let Some((next, _)) = aoc_16::Day16::step(&lighting) {
+        lighting = next;
+    }
+    assert_eq!(lighting.beams.energized.len(), 46);
+
+    let (pulses, events) = run::<aoc_20::Day20>(&example(20), 12);
+    assert_eq!((pulses.presses, pulses.counts, pulses.queue.len()), (1, [8, 4], 0));
+    assert_eq!(events[0], ["button press 1", "button -low-> broadcaster"]);
+    assert_eq!(events[10], ["c -low-> inv"]);
+
+    let (walk, _) = run::<aoc_21::Day21>(&example(21), 6);
+    assert_eq!(walk.plots.len(), 16);
+
+    let settled = aoc_22::parse(&example(22)).unwrap();
+    let (settling, events) = run::<aoc_22::Day22>(&example(22), settled.len());
+    assert_eq!(settling.blocks, settled);
+    assert!(aoc_22::Day22::step(&settling).is_none());
+    assert_eq!(events[0], ["brick 1 (A) falls 0 to z=1 and rests on the ground"]);
+}
+
+#[test]
+fn stepper_goes_back_and_forth_and_stops_at_breakpoints() {
+    let day = registry::find(20).unwrap();
+    let mut stepper = Stepper::new((day.simulate.unwrap())(&example(20)).unwrap(), Vec::new());
+    for key in [Key::Char('1'), Key::Char('2'), Key::Right, Key::Left, Key::Char('h')] {
+        assert!(stepper.key(key));
+    }
+    assert_eq!(stepper.steps.position(), 10);
+    assert_eq!(stepper.steps.events(), ["b -low-> c"]);
+
+    for key in "binv -high->\r".chars().map(|c| if c == '\r' { Key::Enter } else { Key::Char(c) }) {
+        stepper.key(key);
+    }
+    assert_eq!(stepper.breakpoints, ["inv -high->"]);
+    stepper.key(Key::Char('c'));
+    assert_eq!(stepper.steps.position(), 12);
+    assert_eq!(stepper.steps.events(), ["inv -high-> a"]);
+
+    let screen = stepper.screen(30, 12);
+    assert!(screen.lines().count() <= 12);
+    assert!(screen.contains("step 12"));
+    assert!(!stepper.key(Key::Char('q')));
+}
+
+#[test]
+fn simulation_days_start_from_their_example() {
+    for day in registry::DAYS.iter().filter(|day| day.simulate.is_some()) {
+        let mut steps = (day.simulate.unwrap())(&example(day.number)).unwrap();
+        assert_eq!(steps.position(), 0, "day {}", day.number);
+        assert!(steps.forward(), "day {}", day.number);
+        assert!(!steps.events().is_empty(), "day {}", day.number);
+        assert!(steps.back() && !steps.back(), "day {}", day.number);
+        assert!(steps.picture().width() > 0, "day {}", day.number);
+    }
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::store::{Fetcher, HttpFetcher, Store, YEAR};
+use common::empty_dir;
+
+/// A stand-in for the puzzle server that answers every request with `status` and the requested path, and remembers
+/// the requests.
+fn serve(status: u16) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+    let seen = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(&stream);
+            while reader.read_line(&mut request).unwrap() > 0 && !request.ends_with("\r\n\r\n") {}
+            let path = request.split(' ').nth(1).unwrap_or("").to_string();
+            seen.lock().unwrap().push(request);
+            let body = format!("input of {path}\n");
+            let response = format!("HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn empty_store(name: &str) -> (Store, PathBuf) {
+    let root = empty_dir(&format!("store-{name}"));
+    (Store::new(&root), root)
+}
+
+struct Offline;
+
+impl Fetcher for Offline {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        panic!("fetched day {day} of {year}");
+    }
+}
+
+#[test]
+fn inputs_are_fetched_once_with_the_session_and_then_read_from_the_store() {
+    let (base_url, requests) = serve(200);
+    let (store, root) = empty_store("fetch");
+    let fetcher = HttpFetcher::new(&base_url, "secret\n", Duration::ZERO);
+
+    let path = store.get_or_fetch(YEAR, 3, "alice", &fetcher).unwrap();
+    assert_eq!(path, root.join("2023/03/alice.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "input of /2023/day/3/input\n");
+    assert_eq!(store.get_or_fetch(YEAR, 3, "alice", &fetcher).unwrap(), path);
+    assert_eq!(store.get_or_fetch(YEAR, 3, "alice", &Offline).unwrap(), path);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /2023/day/3/input "), "{}", requests[0]);
+    assert!(requests[0].to_lowercase().contains("cookie: session=secret\r\n"), "{}", requests[0]);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn users_have_their_own_inputs() {
+    let (store, root) = empty_store("users");
+    store.put(YEAR, 5, "alice", "alice's input\n").unwrap();
+    assert!(store.get(YEAR, 5, "bob").is_none());
+    assert!(store.get(YEAR, 5, "alice").is_some());
+    assert!(store.path(YEAR, 5, "../alice").is_err());
+    assert!(store.path(YEAR, 5, "").is_err());
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn failed_fetches_store_nothing() {
+    let (base_url, _) = serve(404);
+    let (store, _) = empty_store("failed");
+    let fetcher = HttpFetcher::new(&base_url, "expired", Duration::ZERO);
+    let error = store.get_or_fetch(YEAR, 7, "alice", &fetcher).unwrap_err();
+    assert!(error.contains("404"), "{error}");
+    assert!(store.get(YEAR, 7, "alice").is_none());
+}
+
+#[test]
+fn requests_wait_for_the_interval() {
+    let (base_url, requests) = serve(200);
+    let fetcher = HttpFetcher::new(&base_url, "secret", Duration::from_millis(300));
+    let start = Instant::now();
+    for day in 1..=3 {
+        fetcher.fetch(YEAR, day).unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(600), "{:?}", start.elapsed());
+    assert_eq!(requests.lock().unwrap().len(), 3);
+}
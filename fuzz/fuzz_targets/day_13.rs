@@ -0,0 +1,3 @@
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| aoc::fuzz::solve(aoc::registry::find(13).unwrap(), data));